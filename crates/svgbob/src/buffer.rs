@@ -1,4 +1,4 @@
-pub use cell_buffer::{Cell, CellBuffer, CellGrid, Contacts, Span};
+pub use cell_buffer::{Cell, CellBuffer, CellGrid, Contacts, Repair, Span};
 pub use fragment_buffer::{
     fragment, fragment::Fragment, Direction, FragmentBuffer, FragmentSpan,
 };
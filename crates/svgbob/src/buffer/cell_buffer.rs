@@ -3,23 +3,27 @@ use crate::{
         fragment_buffer::{FragmentSpan, FragmentTree},
         Fragment, StringBuffer,
     },
-    fragment::CellText,
+    fragment::{CellText, Chain},
+    map::braille_map::BRAILLE_BLANK,
+    settings::{BASE_SCALE, BASE_STROKE_WIDTH},
     util::parser,
-    Merge, Settings,
+    ArrowHead, Merge, Settings,
 };
 pub use cell::{Cell, CellGrid};
 pub use contacts::Contacts;
 pub use endorse::Endorse;
 use itertools::Itertools;
+pub use repair::Repair;
 use sauron::{
     html,
     html::attributes::{class, id},
     html::*,
     svg::{attributes::*, *},
-    Node,
+    Attribute, Node,
 };
 pub use span::Span;
 use std::{
+    borrow::Cow,
     collections::BTreeMap,
     fmt,
     ops::{Deref, DerefMut},
@@ -28,8 +32,13 @@ use unicode_width::UnicodeWidthStr;
 
 mod cell;
 mod contacts;
+mod dotted_lines;
 mod endorse;
+mod legend;
+mod repair;
+mod spaced_lines;
 mod span;
+mod words;
 
 /// The simplest buffer.
 /// This is maps which char belong to which cell skipping the whitespaces
@@ -105,12 +114,19 @@ impl CellBuffer {
     ) -> (Node<MSG>, f32, f32) {
         let (w, h) = self.get_size(settings);
 
-        let legend_css = self.legend_css();
+        let legend_css = self.legend_css(settings);
+        let legend_defs = self.legend_defs(settings);
         let (group_nodes, fragments) = self.group_nodes_and_fragments(settings);
 
-        let svg_node =
-            Self::fragments_to_node(fragments, legend_css, settings, w, h)
-                .with_children(group_nodes);
+        let svg_node = Self::fragments_to_node_with_defs(
+            fragments,
+            legend_css,
+            legend_defs,
+            settings,
+            w,
+            h,
+        )
+        .with_children(group_nodes);
         (svg_node, w, h)
     }
 
@@ -121,18 +137,34 @@ impl CellBuffer {
         w: f32,
         h: f32,
     ) -> Node<MSG> {
-        let legend_css = self.legend_css();
+        let legend_css = self.legend_css(settings);
+        let legend_defs = self.legend_defs(settings);
         let (group_nodes, fragments) = self.group_nodes_and_fragments(settings);
 
-        let svg_node =
-            Self::fragments_to_node(fragments, legend_css, settings, w, h)
-                .with_children(group_nodes);
+        let svg_node = Self::fragments_to_node_with_defs(
+            fragments,
+            legend_css,
+            legend_defs,
+            settings,
+            w,
+            h,
+        )
+        .with_children(group_nodes);
 
         svg_node
     }
 
     /// return the fragments that are (close objects, touching grouped fragments)
+    /// using the default settings
     pub fn get_fragment_spans(&self) -> (Vec<FragmentSpan>, Vec<Span>) {
+        self.get_fragment_spans_with_settings(&Settings::default())
+    }
+
+    /// return the fragments that are (close objects, touching grouped fragments)
+    pub fn get_fragment_spans_with_settings(
+        &self,
+        settings: &Settings,
+    ) -> (Vec<FragmentSpan>, Vec<Span>) {
         let escaped_text = self.escaped_text_nodes();
 
         let group_adjacents = Vec::<Span>::from(self);
@@ -141,7 +173,7 @@ impl CellBuffer {
             Vec<Vec<Span>>,
         ) = group_adjacents
             .into_iter()
-            .map(|span| span.endorse())
+            .map(|span| span.endorse(settings))
             .map(|endorse| (endorse.accepted, endorse.rejects))
             .unzip();
 
@@ -156,8 +188,11 @@ impl CellBuffer {
     }
 
     /// return fragments that are Rect, Circle,
-    pub(crate) fn into_shapes_fragment(&self) -> Vec<FragmentSpan> {
-        let endorse = self.endorse_to_fragment_spans();
+    pub(crate) fn into_shapes_fragment(
+        &self,
+        settings: &Settings,
+    ) -> Vec<FragmentSpan> {
+        let endorse = self.endorse_to_fragment_spans(settings);
         endorse
             .accepted
             .into_iter()
@@ -165,28 +200,79 @@ impl CellBuffer {
             .collect()
     }
 
+    /// the suggested fixes to the ascii art of the rects and circles
+    /// which are drawn with small gaps or misalignments.
+    /// These are only found when `tolerant_shapes` is set in the settings.
+    pub fn get_repairs(&self, settings: &Settings) -> Vec<Repair> {
+        self.endorse_to_fragment_spans(settings).repairs
+    }
+
+    /// take out the cells of these fragments from the cell buffer
+    fn remove_cells(
+        cell_buffer: &mut Cow<CellBuffer>,
+        fragments: &[FragmentSpan],
+    ) {
+        if fragments.is_empty() {
+            return;
+        }
+        let cell_buffer = cell_buffer.to_mut();
+        for cell in fragments.iter().flat_map(|frag| frag.cells()) {
+            cell_buffer.remove(&cell);
+        }
+    }
+
     /// returns (single_member, grouped,  rest of the fragments
     fn endorse_to_fragment_spans(
         &self,
+        settings: &Settings,
     ) -> Endorse<FragmentSpan, Vec<FragmentSpan>> {
+        // the cell buffer is only copied when some of its cells are taken out
+        let mut cell_buffer = Cow::Borrowed(self);
+        // the cells that matches the stencils are replaced first
+        let stencil_fragments = if settings.stencils.is_empty() {
+            vec![]
+        } else {
+            settings.stencils.endorse(cell_buffer.to_mut())
+        };
+        // then the lines of spaced out dashes, which are not adjacent
+        let spaced_line_fragments =
+            spaced_lines::endorse_spaced_lines(&cell_buffer);
+        Self::remove_cells(&mut cell_buffer, &spaced_line_fragments);
+        // and the lines of dots, before the dots in the prose are taken as words
+        let dotted_line_fragments =
+            dotted_lines::endorse_dotted_lines(&cell_buffer);
+        Self::remove_cells(&mut cell_buffer, &dotted_line_fragments);
+        // then the words, so their punctuations are not drawn
+        let word_fragments = words::endorse_words(&cell_buffer, settings);
+        Self::remove_cells(&mut cell_buffer, &word_fragments);
+
         // endorsed_fragments are the fragment result of successful endorsement
         //
         // vec_groups are not endorsed, but are still touching, these will be grouped together in
         // the svg node
-        let group_adjacents: Vec<Span> = self.into();
+        let group_adjacents: Vec<Span> = cell_buffer.as_ref().into();
+        let endorsed: Vec<Endorse<FragmentSpan, Span>> = group_adjacents
+            .into_iter()
+            .map(|span| span.endorse(settings))
+            .collect();
+        let repairs: Vec<Repair> = endorsed
+            .iter()
+            .flat_map(|endorse| endorse.repairs.iter().copied())
+            .collect();
         let (endorsed_fragments, vec_contacts): (
             Vec<Vec<FragmentSpan>>,
             Vec<Vec<Contacts>>,
-        ) = group_adjacents
+        ) = endorsed
             .into_iter()
-            .map(|span| span.endorse())
             .map(|endorse| {
                 (
                     endorse.accepted,
                     endorse
                         .rejects
                         .into_iter()
-                        .flat_map(|span| <Vec<Contacts>>::from(span))
+                        .flat_map(|span| {
+                            span.into_contacts(&settings.character_map)
+                        })
                         .collect::<Vec<Contacts>>(),
                 )
             })
@@ -205,17 +291,28 @@ impl CellBuffer {
             .flat_map(|contact| contact.as_ref().to_vec())
             .collect();
 
+        // the lines and arcs connected end to end in a group are drawn
+        // as a single path
         let rejects: Vec<Vec<FragmentSpan>> = vec_groups
             .into_iter()
-            .map(|contact| contact.as_ref().to_vec())
+            .map(|contact| {
+                Chain::chain_fragment_spans(contact.as_ref().to_vec(), settings)
+            })
             .collect();
 
-        let accepted: Vec<FragmentSpan> = endorsed_fragments
+        let accepted: Vec<FragmentSpan> = stencil_fragments
             .into_iter()
-            .flatten()
+            .chain(spaced_line_fragments)
+            .chain(dotted_line_fragments)
+            .chain(endorsed_fragments.into_iter().flatten())
             .chain(single_member_fragments.into_iter())
+            .chain(word_fragments)
             .collect();
-        Endorse { accepted, rejects }
+        Endorse {
+            accepted,
+            rejects,
+            repairs,
+        }
     }
 
     /// group nodes that can be group and the rest will be fragments
@@ -225,7 +322,9 @@ impl CellBuffer {
         settings: &Settings,
     ) -> (Vec<Node<MSG>>, Vec<FragmentSpan>) {
         let escaped_text_nodes = self.escaped_text_nodes();
-        let Endorse { accepted, rejects } = self.endorse_to_fragment_spans();
+        let Endorse {
+            accepted, rejects, ..
+        } = self.endorse_to_fragment_spans(settings);
 
         // grouped fragments will be rendered as svg groups
         let group_nodes: Vec<Node<MSG>> = rejects
@@ -233,9 +332,11 @@ impl CellBuffer {
             .map(move |fragments| {
                 let group_members = fragments
                     .iter()
-                    .map(move |gfrag| {
-                        let scaled = gfrag.scale(settings.scale);
-                        let node: Node<MSG> = scaled.fragment.into();
+                    .flat_map(move |gfrag| {
+                        gfrag.scale(settings.scale).with_arrow_head(settings)
+                    })
+                    .map(|gfrag| {
+                        let node: Node<MSG> = gfrag.fragment.into();
                         node
                     })
                     .collect::<Vec<Node<MSG>>>();
@@ -258,22 +359,38 @@ impl CellBuffer {
     }
 
     /// construct the css from the # Legend: of the diagram
-    fn legend_css(&self) -> String {
+    fn legend_css(&self, settings: &Settings) -> String {
         let classes: Vec<String> = self
             .css_styles
             .iter()
             .map(|(class, styles)| {
+                let (styles, _fill) =
+                    legend::extract_fill(class, styles, settings);
                 format!(".svgbob .{}{{ {} }}", class, styles)
             })
             .collect();
         classes.join("\n")
     }
 
+    /// the definitions of the patterns and gradients used as fill
+    /// in the # Legend: of the diagram
+    fn legend_defs<MSG>(&self, settings: &Settings) -> Vec<Node<MSG>> {
+        self.css_styles
+            .iter()
+            .filter_map(|(class, styles)| {
+                let (_styles, fill) =
+                    legend::extract_fill(class, styles, settings);
+                fill.map(|fill| fill.to_def(&legend::fill_id(class), settings))
+            })
+            .collect()
+    }
+
     fn style<MSG>(settings: &Settings, legend_css: String) -> Node<MSG> {
         use sauron::html::units::px;
 
         let stroke_color = settings.stroke_color.to_owned();
         let stroke_width = settings.stroke_width.to_owned();
+        let heavy_stroke_width = settings.heavy_stroke_width.to_owned();
         let background = settings.background.to_owned();
         let fill_color = settings.fill_color.to_owned();
         let font_family = settings.font_family.to_owned();
@@ -300,6 +417,10 @@ impl CellBuffer {
                     font_size: px(font_size),
                 },
 
+                ".svgbob text.compartment_0, .svgbob text.header": {
+                    font_weight: "bold",
+                },
+
                 ".svgbob rect.backdrop":{
                     stroke: "none",
                     fill: background.clone(),
@@ -309,6 +430,33 @@ impl CellBuffer {
                     stroke_dasharray: 8,
                 },
 
+                ".svgbob .dotted":{
+                    stroke_dasharray: "1 4",
+                },
+
+                ".svgbob .dash_dot":{
+                    stroke_dasharray: "8 4 1 4",
+                },
+
+                ".svgbob .heavy, .svgbob .table_header":{
+                    stroke_width: heavy_stroke_width,
+                },
+
+                ".svgbob .light_shade":{
+                    fill_opacity: 0.25,
+                    stroke: "none",
+                },
+
+                ".svgbob .medium_shade":{
+                    fill_opacity: 0.5,
+                    stroke: "none",
+                },
+
+                ".svgbob .dark_shade":{
+                    fill_opacity: 0.75,
+                    stroke: "none",
+                },
+
                 ".svgbob .filled":{
                     fill: fill_color,
                 },
@@ -330,6 +478,30 @@ impl CellBuffer {
                     marker_start: "url(#arrow)",
                 },
 
+                ".svgbob .end_marked_clear_arrow":{
+                    marker_end: "url(#clear_arrow)",
+                },
+
+                ".svgbob .start_marked_clear_arrow":{
+                    marker_start: "url(#clear_arrow)",
+                },
+
+                ".svgbob .end_marked_open_diamond":{
+                    marker_end: "url(#open_diamond)",
+                },
+
+                ".svgbob .start_marked_open_diamond":{
+                    marker_start: "url(#open_diamond)",
+                },
+
+                ".svgbob .end_marked_filled_diamond":{
+                    marker_end: "url(#filled_diamond)",
+                },
+
+                ".svgbob .start_marked_filled_diamond":{
+                    marker_start: "url(#filled_diamond)",
+                },
+
                 ".svgbob .end_marked_diamond":{
                     marker_end: "url(#diamond)",
                 },
@@ -359,6 +531,46 @@ impl CellBuffer {
 
                 ".svgbob .start_marked_big_open_circle": {
                     marker_start: "url(#big_open_circle)",
+                },
+
+                ".svgbob .end_marked_one":{
+                    marker_end: "url(#one)",
+                },
+
+                ".svgbob .start_marked_one":{
+                    marker_start: "url(#one)",
+                },
+
+                ".svgbob .end_marked_many":{
+                    marker_end: "url(#many)",
+                },
+
+                ".svgbob .start_marked_many":{
+                    marker_start: "url(#many)",
+                },
+
+                ".svgbob .end_marked_zero_or_one":{
+                    marker_end: "url(#zero_or_one)",
+                },
+
+                ".svgbob .start_marked_zero_or_one":{
+                    marker_start: "url(#zero_or_one)",
+                },
+
+                ".svgbob .end_marked_one_or_many":{
+                    marker_end: "url(#one_or_many)",
+                },
+
+                ".svgbob .start_marked_one_or_many":{
+                    marker_start: "url(#one_or_many)",
+                },
+
+                ".svgbob .end_marked_zero_or_many":{
+                    marker_end: "url(#zero_or_many)",
+                },
+
+                ".svgbob .start_marked_zero_or_many":{
+                    marker_start: "url(#zero_or_many)",
                 }
         };
 
@@ -376,10 +588,32 @@ impl CellBuffer {
         settings: &Settings,
         w: f32,
         h: f32,
+    ) -> Node<MSG> {
+        Self::fragments_to_node_with_defs(
+            fragments,
+            legend_css,
+            vec![],
+            settings,
+            w,
+            h,
+        )
+    }
+
+    /// convert the fragments into svg nodes, along with the `legend_defs`,
+    /// the patterns and gradients used as fill in the # Legend:,
+    /// which are added to the defs of the svg
+    pub fn fragments_to_node_with_defs<MSG>(
+        fragments: Vec<FragmentSpan>,
+        legend_css: String,
+        legend_defs: Vec<Node<MSG>>,
+        settings: &Settings,
+        w: f32,
+        h: f32,
     ) -> Node<MSG> {
         let fragments_scaled: Vec<FragmentSpan> = fragments
             .into_iter()
             .map(|frag| frag.scale(settings.scale))
+            .flat_map(|frag| frag.with_arrow_head(settings))
             .collect();
         let fragment_nodes: Vec<Node<MSG>> =
             FragmentTree::fragments_to_node(fragments_scaled);
@@ -389,7 +623,7 @@ impl CellBuffer {
             children.push(Self::style(settings, legend_css));
         }
         if settings.include_defs {
-            children.push(Self::get_defs());
+            children.push(Self::get_defs(settings, legend_defs));
         }
 
         // backdrop needs to appear first before the fragment nodes
@@ -415,60 +649,214 @@ impl CellBuffer {
         )
     }
 
-    fn get_defs<MSG>() -> Node<MSG> {
+    fn get_defs<MSG>(
+        settings: &Settings,
+        legend_defs: Vec<Node<MSG>>,
+    ) -> Node<MSG> {
         defs(
             [],
             [
-                Self::arrow_marker(),
-                Self::diamond_marker(),
-                Self::circle_marker(),
-                Self::open_circle_marker(),
-                Self::big_open_circle_marker(),
-            ],
+                Self::arrow_marker(settings),
+                Self::diamond_marker(settings),
+                Self::circle_marker(settings),
+                Self::open_circle_marker(settings),
+                Self::big_open_circle_marker(settings),
+                Self::clear_arrow_marker(settings),
+                Self::open_diamond_marker(settings),
+                Self::filled_diamond_marker(settings),
+                Self::one_marker(settings),
+                Self::many_marker(settings),
+                Self::zero_or_one_marker(settings),
+                Self::one_or_many_marker(settings),
+                Self::zero_or_many_marker(settings),
+            ]
+            .into_iter()
+            .chain(legend_defs),
         )
     }
 
-    fn arrow_marker<MSG>() -> Node<MSG> {
+    /// a marker which is `w` by `h` stroke widths at the default settings,
+    /// resized with the marker scale of the settings.
+    /// The markers which don't grow with the stroke width are sized relative
+    /// to the cell instead, keeping their size at the default stroke width.
+    fn sized_marker<MSG>(
+        settings: &Settings,
+        attributes: [Attribute<MSG>; 4],
+        (w, h): (f32, f32),
+        children: impl IntoIterator<Item = Node<MSG>>,
+    ) -> Node<MSG> {
+        let size = if settings.scale_markers_with_stroke {
+            settings.marker_scale
+        } else {
+            settings.marker_scale * BASE_STROKE_WIDTH * settings.scale
+                / BASE_SCALE
+        };
+        let marker_units = (!settings.scale_markers_with_stroke)
+            .then(|| marker_units("userSpaceOnUse"));
         marker(
-            [
-                id("arrow"),
-                view_box("-2 -2 8 8"),
-                ref_x(4),
-                ref_y(2),
-                marker_width(7),
-                marker_height(7),
-                orient("auto-start-reverse"),
-            ],
-            [polygon([points("0,0 0,4 4,2 0,0")], [])],
+            attributes
+                .into_iter()
+                .chain([
+                    marker_width(w * size),
+                    marker_height(h * size),
+                    orient("auto-start-reverse"),
+                ])
+                .chain(marker_units),
+            children,
         )
     }
 
-    fn diamond_marker<MSG>() -> Node<MSG> {
-        marker(
-            [
-                id("diamond"),
-                view_box("-2 -2 8 8"),
-                ref_x(4),
-                ref_y(2),
-                marker_width(7),
-                marker_height(7),
-                orient("auto-start-reverse"),
-            ],
+    /// the crow's foot markers are drawn with the entity on the right side,
+    /// where the marker is attached to
+    fn crows_foot_marker<MSG>(
+        settings: &Settings,
+        marker_id: &'static str,
+        children: Vec<Node<MSG>>,
+    ) -> Node<MSG> {
+        Self::sized_marker(
+            settings,
+            [id(marker_id), view_box("0 0 20 8"), ref_x(20), ref_y(4)],
+            (10.0, 4.0),
+            children,
+        )
+    }
+
+    /// the crow's foot, which fans out to the entity
+    fn crows_foot<MSG>() -> Node<MSG> {
+        path([d("M8,4 L20,0 M8,4 L20,4 M8,4 L20,8")], [])
+    }
+
+    /// a bar across the line
+    fn crows_foot_bar<MSG>(x: i32) -> Node<MSG> {
+        path([d(format!("M{x},0 L{x},8"))], [])
+    }
+
+    /// the open circle for the zero cardinality
+    fn crows_foot_circle<MSG>(x: i32) -> Node<MSG> {
+        circle(
+            [cx(x), cy(4), r(3), html::attributes::class("bg_filled")],
+            [],
+        )
+    }
+
+    fn one_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::crows_foot_marker(
+            settings,
+            "one",
+            vec![Self::crows_foot_bar(12), Self::crows_foot_bar(16)],
+        )
+    }
+
+    fn many_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::crows_foot_marker(settings, "many", vec![Self::crows_foot()])
+    }
+
+    fn zero_or_one_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::crows_foot_marker(
+            settings,
+            "zero_or_one",
+            vec![Self::crows_foot_circle(6), Self::crows_foot_bar(16)],
+        )
+    }
+
+    fn one_or_many_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::crows_foot_marker(
+            settings,
+            "one_or_many",
+            vec![Self::crows_foot_bar(5), Self::crows_foot()],
+        )
+    }
+
+    fn zero_or_many_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::crows_foot_marker(
+            settings,
+            "zero_or_many",
+            vec![Self::crows_foot_circle(4), Self::crows_foot()],
+        )
+    }
+
+    /// the arrow head, in the shape chosen in the settings
+    fn arrow_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        let arrow_head = match settings.arrow_head {
+            ArrowHead::Triangle => polygon([points("0,0 0,4 4,2 0,0")], []),
+            ArrowHead::Chevron => path([d("M0,0 L4,2 L0,4"), fill("none")], []),
+            ArrowHead::Barbed => polygon([points("0,0 4,2 0,4 1,2 0,0")], []),
+            ArrowHead::Hollow => polygon(
+                [points("0,0 0,4 4,2 0,0"), html::attributes::class("nofill")],
+                [],
+            ),
+        };
+        Self::sized_marker(
+            settings,
+            [id("arrow"), view_box("-2 -2 8 8"), ref_x(4), ref_y(2)],
+            (7.0, 7.0),
+            [arrow_head],
+        )
+    }
+
+    fn diamond_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::sized_marker(
+            settings,
+            [id("diamond"), view_box("-2 -2 8 8"), ref_x(4), ref_y(2)],
+            (7.0, 7.0),
             [polygon([points("0,2 2,0 4,2 2,4 0,2")], [])],
         )
     }
 
-    fn open_circle_marker<MSG>() -> Node<MSG> {
-        marker(
+    /// the hollow triangle of the inheritance in UML class diagrams
+    fn clear_arrow_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::sized_marker(
+            settings,
             [
-                id("open_circle"),
-                view_box("0 0 8 8"),
-                ref_x(4),
-                ref_y(4),
-                marker_width(7),
-                marker_height(7),
-                orient("auto-start-reverse"),
+                id("clear_arrow"),
+                view_box("0 0 16 16"),
+                ref_x(16),
+                ref_y(8),
             ],
+            (8.0, 8.0),
+            [polygon(
+                [
+                    points("2,2 16,8 2,14 2,2"),
+                    html::attributes::class("bg_filled"),
+                ],
+                [],
+            )],
+        )
+    }
+
+    /// the diamonds of the aggregation and composition in UML class diagrams
+    fn uml_diamond_marker<MSG>(
+        settings: &Settings,
+        marker_id: &'static str,
+        fill_class: &'static str,
+    ) -> Node<MSG> {
+        Self::sized_marker(
+            settings,
+            [id(marker_id), view_box("0 0 20 12"), ref_x(20), ref_y(6)],
+            (10.0, 6.0),
+            [polygon(
+                [
+                    points("1,6 10,1 19,6 10,11 1,6"),
+                    html::attributes::class(fill_class),
+                ],
+                [],
+            )],
+        )
+    }
+
+    fn open_diamond_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::uml_diamond_marker(settings, "open_diamond", "bg_filled")
+    }
+
+    fn filled_diamond_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::uml_diamond_marker(settings, "filled_diamond", "filled")
+    }
+
+    fn open_circle_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::sized_marker(
+            settings,
+            [id("open_circle"), view_box("0 0 8 8"), ref_x(4), ref_y(4)],
+            (7.0, 7.0),
             [circle(
                 [cx(4), cy(4), r(2), html::attributes::class("bg_filled")],
                 [],
@@ -476,17 +864,11 @@ impl CellBuffer {
         )
     }
 
-    fn circle_marker<MSG>() -> Node<MSG> {
-        marker(
-            [
-                id("circle"),
-                view_box("0 0 8 8"),
-                ref_x(4),
-                ref_y(4),
-                marker_width(7),
-                marker_height(7),
-                orient("auto-start-reverse"),
-            ],
+    fn circle_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::sized_marker(
+            settings,
+            [id("circle"), view_box("0 0 8 8"), ref_x(4), ref_y(4)],
+            (7.0, 7.0),
             [circle(
                 [cx(4), cy(4), r(2), html::attributes::class("filled")],
                 [],
@@ -494,17 +876,16 @@ impl CellBuffer {
         )
     }
 
-    fn big_open_circle_marker<MSG>() -> Node<MSG> {
-        marker(
+    fn big_open_circle_marker<MSG>(settings: &Settings) -> Node<MSG> {
+        Self::sized_marker(
+            settings,
             [
                 id("big_open_circle"),
                 view_box("0 0 8 8"),
                 ref_x(4),
                 ref_y(4),
-                marker_width(7),
-                marker_height(7),
-                orient("auto-start-reverse"),
             ],
+            (7.0, 7.0),
             [circle(
                 [cx(4), cy(4), r(3), html::attributes::class("bg_filled")],
                 [],
@@ -604,7 +985,7 @@ impl From<StringBuffer> for CellBuffer {
             buffer.escaped_text.extend(escaped_text);
 
             for (x, ch) in unescaped.chars().enumerate() {
-                if ch != '\0' && !ch.is_whitespace() {
+                if ch != '\0' && !ch.is_whitespace() && ch != BRAILLE_BLANK {
                     let cell = Cell::new(x as i32, y as i32);
                     buffer.insert(cell, ch);
                 }
@@ -727,7 +1108,7 @@ This is a text
 
     "#;
         let buffer = CellBuffer::from(art);
-        let shapes = buffer.into_shapes_fragment();
+        let shapes = buffer.into_shapes_fragment(&Settings::default());
         println!("shapes: {:#?}", shapes);
         assert_eq!(2, shapes.len());
     }
@@ -741,7 +1122,7 @@ This is a text
 
     "#;
         let buffer = CellBuffer::from(art);
-        let shapes = buffer.into_shapes_fragment();
+        let shapes = buffer.into_shapes_fragment(&Settings::default());
         println!("shapes: {:#?}", shapes);
         assert_eq!(1, shapes.len());
         assert!(shapes[0]
@@ -749,6 +1130,93 @@ This is a text
             .hit(Cell::new(15, 1).a(), Cell::new(15, 1).y()));
     }
 
+    #[test]
+    fn test_shapes_fragment_with_stencils() {
+        let mut stencils = crate::StencilRegistry::new();
+        stencils.register(crate::Stencil::with_fragments(
+            "box",
+            "[#]",
+            vec![crate::fragment::rect(
+                CellGrid::a(),
+                Cell::new(2, 0).y(),
+                false,
+                false,
+            )],
+        ));
+        let settings = Settings {
+            stencils,
+            ..Settings::default()
+        };
+        let buffer = CellBuffer::from("   [#]   ");
+        assert!(buffer.into_shapes_fragment(&Settings::default()).is_empty());
+        assert_eq!(buffer.into_shapes_fragment(&settings).len(), 1);
+    }
+
+    #[test]
+    fn test_fragment_spans_with_character_map() {
+        let mut character_map = crate::CharacterMap::new();
+        character_map.register_fragments(
+            '⁓',
+            vec![crate::fragment::line(CellGrid::k(), CellGrid::o())],
+        );
+        let settings = Settings {
+            character_map,
+            ..Settings::default()
+        };
+        let art = r#"
+            +⁓⁓⁓⁓+
+            |    |
+            +⁓⁓⁓⁓+
+        "#;
+        let buffer = CellBuffer::from(art);
+        let (accepted, _rejects) =
+            buffer.get_fragment_spans_with_settings(&settings);
+        assert!(accepted.iter().any(|frag| frag.fragment.is_rect()));
+        let (accepted, _rejects) = buffer.get_fragment_spans();
+        assert!(!accepted.iter().any(|frag| frag.fragment.is_rect()));
+    }
+
+    #[test]
+    fn test_character_map_is_not_used_by_tables() {
+        let mut character_map = crate::CharacterMap::new();
+        character_map.register_fragments(
+            '⁓',
+            vec![crate::fragment::line(CellGrid::k(), CellGrid::o())],
+        );
+        let settings = Settings {
+            character_map,
+            ..Settings::default()
+        };
+        let art = r#"
+            +⁓⁓⁓⁓+⁓⁓⁓⁓+
+            |    |    |
+            +⁓⁓⁓⁓+⁓⁓⁓⁓+
+        "#;
+        let buffer = CellBuffer::from(art);
+        let spans: Vec<Span> = (&buffer).into();
+        let fragments: Vec<Fragment> = spans
+            .into_iter()
+            .flat_map(|span| span.into_contacts(&settings.character_map))
+            .flat_map(|contacts| {
+                contacts
+                    .fragments()
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        // the registered character is drawn as a line,
+        // but the grid is not recognized as a table
+        assert!(fragments.iter().any(|frag| frag.as_line().is_some()));
+        assert!(!fragments.iter().any(|frag| frag.as_table().is_some()));
+
+        let buffer = CellBuffer::from(art.replace('⁓', "-").as_str());
+        let mut spans: Vec<Span> = (&buffer).into();
+        let (tables, _unmatched) =
+            crate::map::table_map::endorse_tables(&spans.remove(0));
+        assert_eq!(tables.len(), 1);
+    }
+
     /// The . in .-/
     /// will create a new contacts even since it is not adjacent to /
     /// so it needs a second_pass to merge it
@@ -63,21 +63,62 @@ impl Contacts {
     /// can be:
     ///  - rect
     ///  - rounded_rect
+    ///  - rect divided into compartments
+    ///  - closed polygon
+    ///
+    /// The parts of a sequence diagram are already endorsed, since they are
+    /// recognized together with the participant boxes which won't be around
+    /// anymore when the rejects are endorsed again.
     pub(crate) fn endorse_rect(&self) -> Option<Fragment> {
         let fragments = self.fragments();
+        if let [fragment] = fragments.as_slice() {
+            if fragment.is_sequence() {
+                return Some((*fragment).clone());
+            }
+        }
         if let Some(rect) = endorse::endorse_rect(&fragments) {
             Some(rect.into())
-        } else {
+        } else if let Some(rounded_rect) =
             endorse::endorse_rounded_rect(&fragments)
-                .map(|rounded_rect| rounded_rect.into())
+        {
+            Some(rounded_rect.into())
+        } else if let Some(compartmented_rect) =
+            endorse::endorse_compartmented_rect(&fragments)
+        {
+            Some(compartmented_rect.into())
+        } else {
+            endorse::endorse_polygon(&fragments).map(|polygon| polygon.into())
         }
     }
 
-    /// First phase of endorsing to shapes, in this case, rects and rounded_rects
+    /// Endorse the box in isometric view whose edges are in this group,
+    /// returning it along with the rest of the fragments, such as the lines
+    /// connected to the box.
+    pub(crate) fn endorse_isometric_box(
+        &self,
+    ) -> Option<(FragmentSpan, Option<Contacts>)> {
+        let (isometric_box, indexes) =
+            endorse::endorse_isometric_box(&self.fragments())?;
+        let (edges, rest): (Vec<_>, Vec<_>) = self
+            .0
+            .iter()
+            .cloned()
+            .enumerate()
+            .partition(|(index, _)| indexes.contains(index));
+        let edges = Contacts(edges.into_iter().map(|(_, fs)| fs).collect());
+        let rest: Vec<FragmentSpan> =
+            rest.into_iter().map(|(_, fs)| fs).collect();
+        let fragment_span =
+            FragmentSpan::new(edges.span(), isometric_box.into());
+        Some((fragment_span, (!rest.is_empty()).then_some(Contacts(rest))))
+    }
+
+    /// First phase of endorsing to shapes, in this case, rects, rounded_rects
+    /// and closed polygons
     ///
     /// This function is calling on endorse methods that is applicable
     /// to fragments that are touching, to be promoted to a shape.
-    /// These includes: rect, roundedrect,
+    /// These includes: rect, roundedrect, polygon
     pub(crate) fn endorse_rects(
         contacts: Vec<Contacts>,
     ) -> Endorse<FragmentSpan, Contacts> {
@@ -88,11 +129,20 @@ impl Contacts {
                 let span = contact.span();
                 let fragment_span = FragmentSpan::new(span, fragment);
                 accepted.push(fragment_span);
+            } else if let Some((isometric_box, rest)) =
+                contact.endorse_isometric_box()
+            {
+                accepted.push(isometric_box);
+                rejects.extend(rest);
             } else {
                 rejects.push(contact);
             }
         }
-        Endorse { accepted, rejects }
+        Endorse {
+            accepted,
+            rejects,
+            repairs: vec![],
+        }
     }
 
     pub(crate) fn absolute_position(&self, cell: Cell) -> Self {
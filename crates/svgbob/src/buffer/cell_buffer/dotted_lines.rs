@@ -0,0 +1,153 @@
+use crate::{
+    buffer::{FragmentSpan, Span},
+    fragment::dotted_line,
+    Cell,
+};
+use std::collections::BTreeMap;
+
+/// the least number of dots for a run standing on its own to be drawn as a line
+const MIN_DOTS: usize = 3;
+
+/// Find the horizontal lines which are drawn with dots, and take them out of the drawing.
+///
+/// ```ignore
+///    ......      |....|    +.....+    ---.....--->
+/// ```
+/// A run of dots is a line when it is at least 3 dots long with nothing next to it,
+/// or when it connects to the side or corner of a box, or to a line of at least
+/// 2 dashes. A single dash next to the dots is the bottom of a circle, which is
+/// left to the drawing. The dots must also stand alone in their column,
+/// otherwise they are the rounded corners of the shapes above or below them.
+///
+/// The dots in the prose are left as text, these are next to the letters,
+/// the brackets or 1 space away from a word.
+///
+/// ```ignore
+///    ok...   wait ... then   etc..   foo(...)
+/// ```
+///
+/// returns the lines, along with the cells they are made of
+pub(crate) fn endorse_dotted_lines(
+    cells: &BTreeMap<Cell, char>,
+) -> Vec<FragmentSpan> {
+    let starts: Vec<Cell> = cells
+        .iter()
+        .filter(|(cell, ch)| {
+            **ch == '.' && cells.get(&cell.left()) != Some(&'.')
+        })
+        .map(|(cell, _ch)| *cell)
+        .collect();
+    starts
+        .into_iter()
+        .filter_map(|start| dotted_run(cells, start))
+        .collect()
+}
+
+/// the dotted line connects to the character at `cell`, which is next to the run of dots
+/// going to the `direction`
+fn is_connector(
+    cells: &BTreeMap<Cell, char>,
+    cell: Cell,
+    direction: i32,
+) -> bool {
+    match cells.get(&cell) {
+        Some('+') | Some('|') => true,
+        Some('-') => {
+            cells.get(&Cell::new(cell.x + direction, cell.y)) == Some(&'-')
+        }
+        _ => false,
+    }
+}
+
+/// the characters which makes the dots next to it part of a text
+fn is_text(ch: Option<&char>) -> bool {
+    ch.is_some_and(|ch| {
+        ch.is_alphanumeric() || matches!(ch, '(' | ')' | '[' | ']' | '{' | '}')
+    })
+}
+
+/// the dotted line made of the run of dots starting at `start`
+fn dotted_run(
+    cells: &BTreeMap<Cell, char>,
+    start: Cell,
+) -> Option<FragmentSpan> {
+    let mut end = start;
+    while cells.get(&end.right()) == Some(&'.') {
+        end = end.right();
+    }
+    let dots = (end.x - start.x + 1) as usize;
+    let before = cells.get(&start.left());
+    let after = cells.get(&end.right());
+    let connects_before = is_connector(cells, start.left(), -1);
+    let connects_after = is_connector(cells, end.right(), 1);
+
+    let is_connected = connects_before || connects_after;
+    let is_alone = before.is_none()
+        && after.is_none()
+        && !is_text(cells.get(&start.left().left()))
+        && !is_text(cells.get(&end.right().right()));
+    let is_column_alone = (start.x..=end.x).all(|x| {
+        let cell = Cell::new(x, start.y);
+        cells.get(&cell.top()).is_none() && cells.get(&cell.bottom()).is_none()
+    });
+
+    let is_line = is_column_alone
+        && if is_connected {
+            dots >= 2 && !is_text(before) && !is_text(after)
+        } else {
+            is_alone && dots >= MIN_DOTS
+        };
+    if !is_line {
+        return None;
+    }
+    // the line goes to the center of the side or corner it connects to
+    let line_start = if connects_before {
+        start.left().m()
+    } else {
+        start.m()
+    };
+    let line_end = if connects_after {
+        end.right().m()
+    } else {
+        end.m()
+    };
+    let run: Vec<(Cell, char)> = (start.x..=end.x)
+        .map(|x| (Cell::new(x, start.y), '.'))
+        .collect();
+    Some(FragmentSpan::new(
+        Span::from(run),
+        dotted_line(line_start, line_end),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::CellBuffer;
+
+    fn dotted_lines_of(art: &str) -> usize {
+        let cell_buffer = CellBuffer::from(art);
+        endorse_dotted_lines(&cell_buffer).len()
+    }
+
+    #[test]
+    fn test_dotted_lines() {
+        let art = r#"
+    ......     |....|    +.....+    ---.....--->
+            "#;
+        assert_eq!(dotted_lines_of(art), 4);
+    }
+
+    #[test]
+    fn test_dots_in_prose() {
+        let art = r#"
+    ok...   wait ... then   etc..   foo(...)   Hmm..   ..
+
+    '-.....-'
+
+     .--.......--.
+        |||||||
+            "#;
+        assert_eq!(dotted_lines_of(art), 0);
+    }
+}
@@ -1,6 +1,7 @@
+use super::Repair;
 use crate::{
-    fragment::{Bounds, Rect},
-    Fragment,
+    fragment::{Bounds, DashStyle, IsometricBox, Line, Polygon, Rect},
+    util, Fragment, Point,
 };
 
 /// Result of endorsing processes
@@ -10,21 +11,23 @@ pub struct Endorse<T, E> {
     pub accepted: Vec<T>,
     /// The objects that didn't pass the endorsement
     pub rejects: Vec<E>,
+    /// The fixes to the ascii art of the shapes that are accepted
+    /// despite having small gaps or misalignments
+    pub repairs: Vec<Repair>,
 }
 
 impl<T, E> Endorse<T, E> {
     pub fn extend(&mut self, other: Self) {
         self.accepted.extend(other.accepted);
         self.rejects.extend(other.rejects);
+        self.repairs.extend(other.repairs);
     }
 }
 
 /// if a group of fragment can be endorse as rect, return the bounds point for the
 /// rectangle
 pub fn endorse_rect(fragments: &[&Fragment]) -> Option<Rect> {
-    if is_rect(fragments) {
-        let is_any_broken =
-            fragments.iter().any(|fragment| fragment.is_broken());
+    if is_rect(fragments) && is_uniform_weight(fragments) {
         let all_points = fragments.iter().fold(vec![], |mut acc, frag| {
             let (p1, p2) = frag.bounds();
             acc.push(p1);
@@ -34,7 +37,13 @@ pub fn endorse_rect(fragments: &[&Fragment]) -> Option<Rect> {
         let min = all_points.iter().min();
         let max = all_points.iter().max();
         if let (Some(min), Some(max)) = (min, max) {
-            Some(Rect::new(*min, *max, false, is_any_broken))
+            let is_all_heavy =
+                fragments.iter().all(|fragment| fragment.is_heavy());
+            Some(Rect {
+                is_heavy: is_all_heavy,
+                dash: rect_dash(fragments),
+                ..Rect::new(*min, *max, false, false)
+            })
         } else {
             None
         }
@@ -43,6 +52,23 @@ pub fn endorse_rect(fragments: &[&Fragment]) -> Option<Rect> {
     }
 }
 
+/// when any of the sides of the rect is not solid,
+/// then the whole rect is drawn with the dash style of that side
+fn rect_dash(fragments: &[&Fragment]) -> DashStyle {
+    fragments
+        .iter()
+        .map(|fragment| fragment.dash())
+        .find(|dash| *dash != DashStyle::Solid)
+        .unwrap_or_default()
+}
+
+/// the sides of a rect should be either all light or all heavy,
+/// otherwise the sides are drawn as they are
+fn is_uniform_weight(fragments: &[&Fragment]) -> bool {
+    fragments.iter().all(|fragment| fragment.is_heavy())
+        || fragments.iter().all(|fragment| !fragment.is_heavy())
+}
+
 /// group of fragments can be check if they form:
 /// - rectangle
 fn is_rect(fragments: &[&Fragment]) -> bool {
@@ -72,8 +98,6 @@ fn is_rect(fragments: &[&Fragment]) -> bool {
 ///  - each of the right angle touches 2 lines that are aabb_perpendicular
 pub fn endorse_rounded_rect(fragments: &[&Fragment]) -> Option<Rect> {
     if let (true, arc_radius) = is_rounded_rect(fragments) {
-        let is_any_broken =
-            fragments.iter().any(|fragment| fragment.is_broken());
         let all_points = fragments.iter().fold(vec![], |mut acc, frag| {
             let (p1, p2) = frag.bounds();
             acc.push(p1);
@@ -84,13 +108,16 @@ pub fn endorse_rounded_rect(fragments: &[&Fragment]) -> Option<Rect> {
         let max = all_points.iter().max();
         if let (Some(min), Some(max)) = (min, max) {
             //TODO: compute the radius from
-            Some(Rect::rounded_new(
-                *min,
-                *max,
-                false,
-                arc_radius.expect("expecting arc radius"),
-                is_any_broken,
-            ))
+            Some(Rect {
+                dash: rect_dash(fragments),
+                ..Rect::rounded_new(
+                    *min,
+                    *max,
+                    false,
+                    arc_radius.expect("expecting arc radius"),
+                    false,
+                )
+            })
         } else {
             None
         }
@@ -99,6 +126,55 @@ pub fn endorse_rounded_rect(fragments: &[&Fragment]) -> Option<Rect> {
     }
 }
 
+/// if a group of fragments is a rect or rounded rect with horizontal lines
+/// going across its full width, return the rect divided into compartments.
+///
+/// ```ignore
+///   +--------+
+///   | Person |
+///   |--------|
+///   | name   |
+///   +--------+
+/// ```
+pub fn endorse_compartmented_rect(fragments: &[&Fragment]) -> Option<Rect> {
+    let top = fragments
+        .iter()
+        .map(|frag| frag.bounds().0.y)
+        .reduce(f32::min)?;
+    let bottom = fragments
+        .iter()
+        .map(|frag| frag.bounds().1.y)
+        .reduce(f32::max)?;
+    let is_divider = |frag: &Fragment| {
+        frag.as_line().is_some_and(|line| {
+            line.is_horizontal() && line.start.y > top && line.start.y < bottom
+        })
+    };
+    let (dividers, sides): (Vec<&Fragment>, Vec<&Fragment>) =
+        fragments.iter().partition(|frag| is_divider(frag));
+    if dividers.is_empty() || !is_uniform_weight(fragments) {
+        return None;
+    }
+    let rect = endorse_rect(&sides).or_else(|| endorse_rounded_rect(&sides))?;
+    let is_full_width = dividers.iter().all(|divider| {
+        let (start, end) = divider.bounds();
+        start.x == rect.start.x && end.x == rect.end.x
+    });
+    if !is_full_width {
+        return None;
+    }
+    let mut ys: Vec<f32> = dividers
+        .iter()
+        .map(|divider| divider.bounds().0.y)
+        .collect();
+    ys.sort_by(|a, b| util::ord(*a, *b));
+    ys.dedup();
+    Some(Rect {
+        dividers: ys,
+        ..rect
+    })
+}
+
 fn is_rounded_rect(fragments: &[&Fragment]) -> (bool, Option<f32>) {
     if fragments.len() == 8 {
         let parallels = parallel_aabb_group(fragments);
@@ -127,6 +203,344 @@ fn is_rounded_rect(fragments: &[&Fragment]) -> (bool, Option<f32>) {
     }
 }
 
+/// the gap allowed in between the sides of a tolerant rect, in the unit of a
+/// cell width horizontally and of a cell height vertically
+const SIDE_GAP: f32 = 1.0;
+
+/// if a group of lines almost forms a rect, such as when a corner is missing
+/// or a side is offset by a column, return the rect it is meant to be.
+///
+/// ```ignore
+///    -----+     +-----+
+///   |     |     |      |
+///   +-----+     +-----+
+/// ```
+pub fn endorse_tolerant_rect(fragments: &[&Fragment]) -> Option<Rect> {
+    let lines: Vec<&Line> = fragments
+        .iter()
+        .map(|frag| frag.as_line())
+        .collect::<Option<Vec<&Line>>>()?;
+    if !is_uniform_weight(fragments) {
+        return None;
+    }
+    let (start, end) = tolerant_bounds(&lines, 0.0)?;
+    Some(Rect {
+        is_heavy: fragments.iter().all(|fragment| fragment.is_heavy()),
+        dash: rect_dash(fragments),
+        ..Rect::new(start, end, false, false)
+    })
+}
+
+/// if a group of lines and right angle arcs almost forms a rounded rect,
+/// such as when a corner is missing, return the rounded rect it is meant to be.
+///
+/// ```ignore
+///   .-----
+///   |     |
+///   '-----'
+/// ```
+pub fn endorse_tolerant_rounded_rect(fragments: &[&Fragment]) -> Option<Rect> {
+    let (arcs, lines): (Vec<&Fragment>, Vec<&Fragment>) =
+        fragments.iter().partition(|frag| frag.as_arc().is_some());
+    if arcs.is_empty() || arcs.len() > 4 {
+        return None;
+    }
+    let radius = arcs[0].as_arc().expect("expecting an arc").radius;
+    if !arcs.iter().all(|frag| {
+        frag.as_arc().is_some_and(|arc| {
+            arc.is_aabb_right_angle_arc() && arc.radius == radius
+        })
+    }) {
+        return None;
+    }
+    let lines: Vec<&Line> = lines
+        .iter()
+        .map(|frag| frag.as_line())
+        .collect::<Option<Vec<&Line>>>()?;
+    let (start, end) = tolerant_bounds(&lines, radius)?;
+    Some(Rect {
+        dash: rect_dash(fragments),
+        ..Rect::rounded_new(start, end, false, radius, false)
+    })
+}
+
+/// the corners of the rect which the 2 horizontal and 2 vertical lines
+/// are meant to form, while the ends of the lines can be off by a gap.
+///
+/// A side is placed where the ends of both of the lines across it agree on,
+/// when they agree on the center of a cell, otherwise it is placed on the line
+/// along it.
+fn tolerant_bounds(lines: &[&Line], radius: f32) -> Option<(Point, Point)> {
+    let (mut horizontals, mut verticals): (Vec<&Line>, Vec<&Line>) =
+        lines.iter().partition(|line| line.is_horizontal());
+    if horizontals.len() != 2
+        || verticals.len() != 2
+        || !verticals.iter().all(|line| line.is_vertical())
+    {
+        return None;
+    }
+    horizontals.sort_by(|a, b| util::ord(a.start.y, b.start.y));
+    verticals.sort_by(|a, b| util::ord(a.start.x, b.start.x));
+    let x_ends = |line: &Line| {
+        (line.start.x.min(line.end.x), line.start.x.max(line.end.x))
+    };
+    let y_ends = |line: &Line| {
+        (line.start.y.min(line.end.y), line.start.y.max(line.end.y))
+    };
+    let is_cell_center_x = |x: f32| (x - 0.5).fract() == 0.0;
+    let is_cell_center_y = |y: f32| ((y - 1.0) / 2.0).fract() == 0.0;
+    // where both of the ends agree on, or else the line along the side
+    let side = |end1: f32, end2: f32, along: f32, is_center: bool| {
+        if end1 == end2 && is_center {
+            end1
+        } else {
+            along
+        }
+    };
+
+    let (top_start, top_end) = x_ends(horizontals[0]);
+    let (bottom_start, bottom_end) = x_ends(horizontals[1]);
+    let (left_start, left_end) = y_ends(verticals[0]);
+    let (right_start, right_end) = y_ends(verticals[1]);
+
+    let left = side(
+        top_start - radius,
+        bottom_start - radius,
+        verticals[0].start.x,
+        is_cell_center_x(top_start - radius),
+    );
+    let right = side(
+        top_end + radius,
+        bottom_end + radius,
+        verticals[1].start.x,
+        is_cell_center_x(top_end + radius),
+    );
+    let top = side(
+        left_start - radius,
+        right_start - radius,
+        horizontals[0].start.y,
+        is_cell_center_y(left_start - radius),
+    );
+    let bottom = side(
+        left_end + radius,
+        right_end + radius,
+        horizontals[1].start.y,
+        is_cell_center_y(left_end + radius),
+    );
+    // the sides must be further apart than the gaps
+    if right - left < 2.0 * SIDE_GAP || bottom - top < 4.0 * SIDE_GAP {
+        return None;
+    }
+
+    let gap_x = SIDE_GAP + radius;
+    let gap_y = SIDE_GAP * 2.0 + radius;
+    let is_near = |a: f32, b: f32, gap: f32| (a - b).abs() <= gap;
+    let is_closing = [
+        (top_start, left, gap_x),
+        (bottom_start, left, gap_x),
+        (top_end, right, gap_x),
+        (bottom_end, right, gap_x),
+        (verticals[0].start.x, left, SIDE_GAP),
+        (verticals[1].start.x, right, SIDE_GAP),
+        (left_start, top, gap_y),
+        (right_start, top, gap_y),
+        (left_end, bottom, gap_y),
+        (right_end, bottom, gap_y),
+        (horizontals[0].start.y, top, SIDE_GAP * 2.0),
+        (horizontals[1].start.y, bottom, SIDE_GAP * 2.0),
+    ]
+    .iter()
+    .all(|(a, b, gap)| is_near(*a, *b, *gap));
+
+    if is_closing {
+        Some((Point::new(left, top), Point::new(right, bottom)))
+    } else {
+        None
+    }
+}
+
+/// if a group of fragments contains the visible edges of a box in isometric
+/// view, return the box along with the indexes of the fragments which are
+/// its edges.
+///
+/// ```ignore
+///     +--------+
+///    /        /|
+///   +--------+ +---->
+///   |        | +
+///   |        |/
+///   +--------+
+/// ```
+/// qualifications:
+///  - 9 of the fragments are solid light lines
+///  - 3 of them are parallel diagonals going back up and to the right,
+///    from the top-left, top-right and bottom-right corners of the front face
+///  - the rest are the 4 sides of the front face, and the top and right edges
+///    of the back face
+///
+/// The other fragments, such as the lines connected to the box, are not part
+/// of the box and are left to the caller.
+pub fn endorse_isometric_box(
+    fragments: &[&Fragment],
+) -> Option<(IsometricBox, Vec<usize>)> {
+    if fragments.len() < 9 {
+        return None;
+    }
+    let lines: Vec<(usize, &Line)> = fragments
+        .iter()
+        .enumerate()
+        .filter_map(|(index, frag)| frag.as_line().map(|line| (index, line)))
+        .filter(|(_, line)| !line.is_broken() && !line.is_heavy())
+        .collect();
+    let (diagonals, edges): (Vec<_>, Vec<_>) = lines
+        .into_iter()
+        .partition(|(_, line)| !line.is_horizontal() && !line.is_vertical());
+    // the end of the diagonals at the front face and at the back face
+    let depths: Vec<(usize, Point, Point)> = diagonals
+        .iter()
+        .map(|(index, line)| {
+            if line.start.y > line.end.y {
+                (*index, line.start, line.end)
+            } else {
+                (*index, line.end, line.start)
+            }
+        })
+        .collect();
+    let find_edge = |a: Point, b: Point| {
+        edges.iter().find_map(|(index, line)| {
+            ((line.start == a && line.end == b)
+                || (line.start == b && line.end == a))
+                .then_some(*index)
+        })
+    };
+    // try every 3 of the diagonals, since the lines connected to the box
+    // can be diagonals too
+    for (i, first) in depths.iter().enumerate() {
+        for (j, second) in depths.iter().enumerate().skip(i + 1) {
+            for third in depths.iter().skip(j + 1) {
+                let triple = [first, second, third];
+                if let Some((isometric_box, mut indexes)) =
+                    isometric_box_of(triple, find_edge)
+                {
+                    indexes.extend(triple.iter().map(|(index, _, _)| *index));
+                    return Some((isometric_box, indexes));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// the isometric box which has these 3 diagonals as its depth edges,
+/// along with the indexes of the 6 other edges found by `find_edge`
+fn isometric_box_of(
+    diagonals: [&(usize, Point, Point); 3],
+    find_edge: impl Fn(Point, Point) -> Option<usize>,
+) -> Option<(IsometricBox, Vec<usize>)> {
+    let depth = diagonals[0].2 - diagonals[0].1;
+    if depth.x <= 0.0
+        || !diagonals
+            .iter()
+            .all(|(_, front, back)| *back - *front == depth)
+    {
+        return None;
+    }
+    let fronts: Vec<Point> =
+        diagonals.iter().map(|(_, front, _)| *front).collect();
+    let start = fronts
+        .iter()
+        .copied()
+        .min_by(|a, b| util::ord(a.x, b.x).then(util::ord(a.y, b.y)))?;
+    let end = fronts
+        .iter()
+        .copied()
+        .max_by(|a, b| util::ord(a.x, b.x).then(util::ord(a.y, b.y)))?;
+    let top_right = Point::new(end.x, start.y);
+    let bottom_left = Point::new(start.x, end.y);
+    if end.y <= start.y || !fronts.contains(&top_right) {
+        return None;
+    }
+    let indexes = [
+        find_edge(start, top_right)?,
+        find_edge(top_right, end)?,
+        find_edge(bottom_left, end)?,
+        find_edge(start, bottom_left)?,
+        find_edge(start + depth, top_right + depth)?,
+        find_edge(top_right + depth, end + depth)?,
+    ];
+    Some((IsometricBox::new(start, end, depth), indexes.to_vec()))
+}
+
+/// if a group of lines forms a single closed loop, return the polygon
+/// tracing the loop.
+///
+/// qualifications:
+///  - at least 3 fragments, all of them solid lines
+///  - every end point is shared by exactly 2 lines
+///  - following the lines from any one of them leads back to it
+///    after visiting all the other lines
+pub fn endorse_polygon(fragments: &[&Fragment]) -> Option<Polygon> {
+    if fragments.len() < 3 {
+        return None;
+    }
+    let lines: Vec<&Line> = fragments
+        .iter()
+        .map(|frag| frag.as_line())
+        .collect::<Option<Vec<&Line>>>()?;
+    // polygons are only drawn with solid light strokes
+    if lines.iter().any(|line| line.is_broken() || line.is_heavy()) {
+        return None;
+    }
+    let is_degree_two = |p: Point| {
+        lines.iter().filter(|line| line.has_endpoint(p)).count() == 2
+    };
+    if !lines
+        .iter()
+        .all(|line| is_degree_two(line.start) && is_degree_two(line.end))
+    {
+        return None;
+    }
+    let first = lines[0];
+    let mut points = vec![first.start];
+    let mut current = first.end;
+    let mut prev_index = 0;
+    let mut visited = 1;
+    while current != first.start {
+        let (index, next) =
+            lines.iter().enumerate().find(|(index, line)| {
+                *index != prev_index && line.has_endpoint(current)
+            })?;
+        points.push(current);
+        current = if next.start == current {
+            next.end
+        } else {
+            next.start
+        };
+        prev_index = index;
+        visited += 1;
+    }
+    if visited != lines.len() {
+        return None;
+    }
+    Some(Polygon::new(remove_collinear_points(points), false, vec![]))
+}
+
+/// remove the points of a closed loop that lie in between its
+/// previous and next point, since they don't change the outline
+fn remove_collinear_points(points: Vec<Point>) -> Vec<Point> {
+    let n = points.len();
+    points
+        .iter()
+        .enumerate()
+        .filter(|(i, p)| {
+            let prev = points[(i + n - 1) % n];
+            let next = points[(i + 1) % n];
+            !util::is_collinear(&prev, p, &next)
+        })
+        .map(|(_, p)| *p)
+        .collect()
+}
+
 /// return the index of the fragments that are right angle arc
 fn right_angle_arcs(fragments: &[&Fragment]) -> Vec<usize> {
     fragments
@@ -170,7 +584,10 @@ fn parallel_aabb_group(fragments: &[&Fragment]) -> Vec<(usize, usize)> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{buffer::CellGrid, fragment::line};
+    use crate::{
+        buffer::CellGrid,
+        fragment::{heavy_line, line},
+    };
 
     #[test]
     fn test_parallel_grouping() {
@@ -208,6 +625,28 @@ mod tests {
         assert!(is_rect(&[&line_ae, &line_au, &line_uy, &line_ey]));
     }
 
+    #[test]
+    fn heavy_sides_should_be_heavy_rect() {
+        let a = CellGrid::a();
+        let e = CellGrid::e();
+        let u = CellGrid::u();
+        let y = CellGrid::y();
+
+        let line_ae = heavy_line(a, e);
+        let line_uy = heavy_line(u, y);
+        let line_au = heavy_line(a, u);
+        let line_ey = heavy_line(e, y);
+        let rect = endorse_rect(&[&line_ae, &line_au, &line_uy, &line_ey])
+            .expect("must be a rect");
+        assert!(rect.is_heavy);
+
+        // mixed light and heavy sides are not endorsed
+        let light_uy = line(u, y);
+        assert!(
+            endorse_rect(&[&line_ae, &line_au, &light_uy, &line_ey]).is_none()
+        );
+    }
+
     #[test]
     fn parallel_and_perpendicular_but_not_touching_should_not_be_rect() {
         let a = CellGrid::a();
@@ -226,4 +665,152 @@ mod tests {
 
         assert!(!is_rect(&[&line_ae, &line_uy, &line_gq, &line_is]));
     }
+
+    #[test]
+    fn closed_lines_should_be_polygon() {
+        let a = CellGrid::a();
+        let e = CellGrid::e();
+        let o = CellGrid::o();
+        let m = CellGrid::m();
+        let u = CellGrid::u();
+        let y = CellGrid::y();
+
+        //  +---+
+        //  | +-+
+        //  +-+
+        let line_ae = line(a, e);
+        let line_eo = line(e, o);
+        let line_om = line(o, m);
+        let line_my = line(m, y);
+        let line_yu = line(y, u);
+        let line_ua = line(u, a);
+        let polygon = endorse_polygon(&[
+            &line_ae, &line_my, &line_eo, &line_ua, &line_om, &line_yu,
+        ]);
+        assert!(polygon.is_some());
+        assert_eq!(polygon.unwrap().points.len(), 6);
+
+        // an open path is not a polygon
+        assert!(endorse_polygon(&[&line_ae, &line_eo, &line_om]).is_none());
+
+        // a loop with a branching line is not a simple polygon
+        let line_ey = line(e, y);
+        assert!(endorse_polygon(&[
+            &line_ae, &line_ey, &line_yu, &line_ua, &line_eo
+        ])
+        .is_none());
+    }
+
+    #[test]
+    fn rect_with_full_width_divider_should_be_compartmented() {
+        let a = CellGrid::a();
+        let e = CellGrid::e();
+        let k = CellGrid::k();
+        let o = CellGrid::o();
+        let u = CellGrid::u();
+        let y = CellGrid::y();
+
+        let line_ae = line(a, e);
+        let line_au = line(a, u);
+        let line_uy = line(u, y);
+        let line_ey = line(e, y);
+        let line_ko = line(k, o);
+        let rect = endorse_compartmented_rect(&[
+            &line_ae, &line_au, &line_ko, &line_uy, &line_ey,
+        ])
+        .expect("must be a compartmented rect");
+        assert_eq!(rect.start, a);
+        assert_eq!(rect.end, y);
+        assert_eq!(rect.dividers, vec![k.y]);
+        assert_eq!(rect.compartment_at(0.5), Some(0));
+        assert_eq!(rect.compartment_at(1.5), Some(1));
+
+        // a divider that doesn't go across the whole rect
+        let m = CellGrid::m();
+        let line_km = line(k, m);
+        assert!(endorse_compartmented_rect(&[
+            &line_ae, &line_au, &line_km, &line_uy, &line_ey,
+        ])
+        .is_none());
+    }
+
+    #[test]
+    fn almost_closed_lines_should_be_tolerant_rect() {
+        //   -----+
+        //  |     |
+        //  +-----+
+        let top = line(Point::new(1.0, 1.0), Point::new(6.5, 1.0));
+        let right = line(Point::new(6.5, 1.0), Point::new(6.5, 5.0));
+        let bottom = line(Point::new(0.5, 5.0), Point::new(6.5, 5.0));
+        let left = line(Point::new(0.5, 2.0), Point::new(0.5, 5.0));
+        let fragments = [&top, &right, &bottom, &left];
+        assert!(endorse_rect(&fragments).is_none());
+        let rect = endorse_tolerant_rect(&fragments).expect("must be a rect");
+        assert_eq!(rect.start, Point::new(0.5, 1.0));
+        assert_eq!(rect.end, Point::new(6.5, 5.0));
+
+        //  +-----+
+        //  |      |
+        //  +-----+
+        let top = line(Point::new(0.5, 1.0), Point::new(6.5, 1.0));
+        let bottom = line(Point::new(0.5, 5.0), Point::new(6.5, 5.0));
+        let left = line(Point::new(0.5, 1.0), Point::new(0.5, 5.0));
+        let right = line(Point::new(7.5, 2.0), Point::new(7.5, 4.0));
+        let rect = endorse_tolerant_rect(&[&top, &right, &bottom, &left])
+            .expect("must be a rect");
+        assert_eq!(rect.end, Point::new(6.5, 5.0));
+
+        // a side too far off is not a rect
+        let right = line(Point::new(9.5, 2.0), Point::new(9.5, 4.0));
+        assert!(
+            endorse_tolerant_rect(&[&top, &right, &bottom, &left]).is_none()
+        );
+    }
+
+    #[test]
+    fn visible_edges_should_be_isometric_box() {
+        //     +--------+
+        //    /        /|
+        //   +--------+ |
+        //   |        | +
+        //   |        |/
+        //   +--------+
+        let start = Point::new(0.5, 5.0);
+        let top_right = Point::new(9.5, 5.0);
+        let end = Point::new(9.5, 11.0);
+        let bottom_left = Point::new(0.5, 11.0);
+        let depth = Point::new(2.0, -4.0);
+        let edges = [
+            line(start, top_right),
+            line(top_right, end),
+            line(end, bottom_left),
+            line(bottom_left, start),
+            line(start + depth, top_right + depth),
+            line(top_right + depth, end + depth),
+            line(start, start + depth),
+            line(top_right + depth, top_right),
+            line(end, end + depth),
+        ];
+        let fragments: Vec<&Fragment> = edges.iter().collect();
+        let (isometric_box, indexes) =
+            endorse_isometric_box(&fragments).expect("must be a box");
+        assert_eq!(isometric_box.start, start);
+        assert_eq!(isometric_box.end, end);
+        assert_eq!(isometric_box.depth, depth);
+        assert_eq!(indexes.len(), 9);
+
+        // without the edge of the back face, it is not a box
+        assert!(endorse_isometric_box(&fragments[..8]).is_none());
+
+        // the lines connected to the box are left out of it
+        let connector = line(top_right + depth, Point::new(16.5, 1.0));
+        let arm = line(end, Point::new(13.5, 13.0));
+        let fragments: Vec<&Fragment> =
+            edges.iter().chain([&connector, &arm]).collect();
+        let (connected_box, mut indexes) =
+            endorse_isometric_box(&fragments).expect("must be a box");
+        assert_eq!(connected_box, isometric_box);
+        indexes.sort();
+        assert_eq!(indexes, (0..9).collect::<Vec<_>>());
+    }
 }
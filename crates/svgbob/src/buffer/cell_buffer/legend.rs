@@ -0,0 +1,290 @@
+use crate::Settings;
+use sauron::{
+    html::attributes::{id, styles},
+    svg::{attributes::*, *},
+    Node,
+};
+
+/// A fill of a class in the `# Legend:`, which can't be expressed in css
+/// alone and is drawn from a definition in the svg `<defs>` instead.
+///
+/// ```ignore
+/// # Legend:
+/// a = {fill: hatch(red);}
+/// b = {fill: linear_gradient(white, #abadb0);}
+/// ```
+/// The fill of the class is then rewritten into `fill: url(#a_fill)`,
+/// referring to the generated `<pattern>` or gradient.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum LegendFill {
+    /// a repeating pattern drawn with the color
+    Pattern(Pattern, String),
+    /// colors spread evenly from left to right
+    LinearGradient(Vec<String>),
+    /// colors spread evenly from the center outwards
+    RadialGradient(Vec<String>),
+}
+
+/// the built-in patterns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Pattern {
+    /// `hatch`, diagonal lines
+    Hatch,
+    /// `cross_hatch`, diagonal lines crossing each other
+    CrossHatch,
+    /// `dots`
+    Dots,
+    /// `grid`, horizontal and vertical lines
+    Grid,
+}
+
+impl LegendFill {
+    /// parse a fill value such as `hatch(red)`, `dots` or
+    /// `radial-gradient(white, blue)`.
+    /// The patterns use the stroke color when no color is given,
+    /// while the gradients need at least 1 color.
+    pub(crate) fn parse(value: &str, settings: &Settings) -> Option<Self> {
+        let value = value.trim();
+        let (name, args) = match value.find('(') {
+            Some(open) => {
+                let args = value[open + 1..].strip_suffix(')')?;
+                (&value[..open], split_args(args))
+            }
+            None => (value, vec![]),
+        };
+        let name = name.trim().replace('-', "_");
+        let color = || {
+            args.first()
+                .cloned()
+                .unwrap_or_else(|| settings.stroke_color.clone())
+        };
+        let pattern = match name.as_str() {
+            "hatch" => Some(Pattern::Hatch),
+            "cross_hatch" => Some(Pattern::CrossHatch),
+            "dots" => Some(Pattern::Dots),
+            "grid" => Some(Pattern::Grid),
+            _ => None,
+        };
+        if let Some(pattern) = pattern {
+            return (args.len() <= 1).then(|| Self::Pattern(pattern, color()));
+        }
+        match name.as_str() {
+            "linear_gradient" if !args.is_empty() => {
+                Some(Self::LinearGradient(args))
+            }
+            "radial_gradient" if !args.is_empty() => {
+                Some(Self::RadialGradient(args))
+            }
+            _ => None,
+        }
+    }
+
+    /// the `<pattern>` or gradient definition of this fill, which is
+    /// referred to by `def_id`.
+    /// The patterns are tiled at the size of the cell width.
+    pub(crate) fn to_def<MSG>(
+        &self,
+        def_id: &str,
+        settings: &Settings,
+    ) -> Node<MSG> {
+        match self {
+            Self::Pattern(pattern, color) => {
+                Self::pattern_def(def_id, *pattern, color, settings.scale)
+            }
+            Self::LinearGradient(colors) => linear_gradient(
+                [id(def_id.to_string()), x1(0), y1(0), x2(1), y2(0)],
+                Self::stops(colors),
+            ),
+            Self::RadialGradient(colors) => {
+                radial_gradient([id(def_id.to_string())], Self::stops(colors))
+            }
+        }
+    }
+
+    fn pattern_def<MSG>(
+        def_id: &str,
+        kind: Pattern,
+        color: &str,
+        size: f32,
+    ) -> Node<MSG> {
+        // inline styles, so the strokes are not overridden by the
+        // svgbob styles of the lines and paths
+        let stroke = || {
+            styles([
+                ("stroke", color.to_string()),
+                ("stroke-width", "1".to_string()),
+                ("fill", "none".to_string()),
+            ])
+        };
+        let (transform, children) = match kind {
+            Pattern::Hatch => (
+                Some("rotate(45)"),
+                vec![path([d(format!("M0,0 L0,{size}")), stroke()], [])],
+            ),
+            Pattern::CrossHatch => (
+                Some("rotate(45)"),
+                vec![path(
+                    [d(format!("M0,0 L0,{size} M0,0 L{size},0")), stroke()],
+                    [],
+                )],
+            ),
+            Pattern::Dots => (
+                None,
+                vec![circle(
+                    [
+                        cx(size / 2.0),
+                        cy(size / 2.0),
+                        r(1),
+                        styles([("fill", color.to_string())]),
+                    ],
+                    [],
+                )],
+            ),
+            Pattern::Grid => (
+                None,
+                vec![path(
+                    [d(format!("M{size},0 L0,0 L0,{size}")), stroke()],
+                    [],
+                )],
+            ),
+        };
+        pattern(
+            [
+                id(def_id.to_string()),
+                pattern_units("userSpaceOnUse"),
+                width(size),
+                height(size),
+            ]
+            .into_iter()
+            .chain(transform.map(pattern_transform)),
+            children,
+        )
+    }
+
+    /// the colors spread evenly from the start to the end of the gradient
+    fn stops<MSG>(colors: &[String]) -> Vec<Node<MSG>> {
+        let last = (colors.len() - 1).max(1) as f32;
+        colors
+            .iter()
+            .enumerate()
+            .map(|(i, color)| {
+                stop(
+                    [
+                        offset(format!("{}%", i as f32 * 100.0 / last)),
+                        stop_color(color.to_string()),
+                    ],
+                    [],
+                )
+            })
+            .collect()
+    }
+}
+
+/// the id of the definition of the fill of this legend class
+pub(crate) fn fill_id(class: &str) -> String {
+    format!("{class}_fill")
+}
+
+/// find the fill in the styles of the legend class which is a pattern
+/// or a gradient, and point it to the definition of the fill instead.
+///
+/// returns the rewritten styles, and the fill if there is one
+pub(crate) fn extract_fill(
+    class: &str,
+    styles: &str,
+    settings: &Settings,
+) -> (String, Option<LegendFill>) {
+    let mut legend_fill = None;
+    let declarations: Vec<String> = styles
+        .split(';')
+        .map(|declaration| match declaration.split_once(':') {
+            Some((property, value)) if property.trim() == "fill" => {
+                match LegendFill::parse(value, settings) {
+                    Some(fill) => {
+                        legend_fill = Some(fill);
+                        format!("{property}: url(#{})", fill_id(class))
+                    }
+                    None => declaration.to_string(),
+                }
+            }
+            _ => declaration.to_string(),
+        })
+        .collect();
+    (declarations.join(";"), legend_fill)
+}
+
+/// split the arguments at the commas which are not inside parenthesis,
+/// so colors such as `rgb(0,0,255)` are kept whole
+fn split_args(args: &str) -> Vec<String> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut current = String::new();
+    for ch in args.chars() {
+        match ch {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => (),
+        }
+        current.push(ch);
+    }
+    parts.push(current);
+    parts
+        .into_iter()
+        .map(|part| part.trim().to_string())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fills() {
+        let settings = Settings::default();
+        assert_eq!(
+            LegendFill::parse(" hatch(red)", &settings),
+            Some(LegendFill::Pattern(Pattern::Hatch, "red".to_string()))
+        );
+        assert_eq!(
+            LegendFill::parse("cross-hatch", &settings),
+            Some(LegendFill::Pattern(
+                Pattern::CrossHatch,
+                "black".to_string()
+            ))
+        );
+        assert_eq!(
+            LegendFill::parse(
+                "linear_gradient(white, rgb(0, 0, 255))",
+                &settings
+            ),
+            Some(LegendFill::LinearGradient(vec![
+                "white".to_string(),
+                "rgb(0, 0, 255)".to_string()
+            ]))
+        );
+        assert_eq!(LegendFill::parse("radial_gradient()", &settings), None);
+        assert_eq!(LegendFill::parse("#abadb0", &settings), None);
+        assert_eq!(LegendFill::parse("dots(red, blue)", &settings), None);
+    }
+
+    #[test]
+    fn test_extract_fill() {
+        let settings = Settings::default();
+        let (styles, fill) =
+            extract_fill("w", " stroke: blue; fill: grid(#ccc); ", &settings);
+        assert_eq!(styles, " stroke: blue; fill: url(#w_fill); ");
+        assert_eq!(
+            fill,
+            Some(LegendFill::Pattern(Pattern::Grid, "#ccc".to_string()))
+        );
+
+        let (styles, fill) = extract_fill("w", "fill: #abadb0;", &settings);
+        assert_eq!(styles, "fill: #abadb0;");
+        assert_eq!(fill, None);
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    buffer::{CellGrid, Span},
+    fragment::Rect,
+    Cell, CharacterMap,
+};
+use std::{collections::BTreeSet, fmt};
+
+/// A suggestion to fix the ascii art of a shape which is drawn with
+/// small gaps or misalignments, by putting the character `ch` at `cell`.
+/// A space means the character at the cell is to be removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Repair {
+    pub cell: Cell,
+    pub ch: char,
+}
+
+impl Repair {
+    pub fn new(cell: Cell, ch: char) -> Self {
+        Repair { cell, ch }
+    }
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {:?}", self.cell, self.ch)
+    }
+}
+
+/// the repairs needed for the cells in `span` to draw the outline of `rect`.
+///
+/// The outline cells that are missing or don't go along the side are replaced
+/// with the expected character, while the cells of the span off the outline
+/// are removed.
+pub(crate) fn rect_repairs(
+    rect: &Rect,
+    span: &Span,
+    character_map: &CharacterMap,
+) -> Vec<Repair> {
+    let left = rect.start.x.floor() as i32;
+    let right = rect.end.x.floor() as i32;
+    let top = (rect.start.y / 2.0).floor() as i32;
+    let bottom = (rect.end.y / 2.0).floor() as i32;
+    let is_rounded = rect.radius.is_some_and(|radius| radius > 0.0);
+
+    let char_at = |cell: Cell| {
+        span.iter()
+            .find(|(span_cell, _ch)| *span_cell == cell)
+            .map(|(_cell, ch)| *ch)
+    };
+    let is_along = |ch: char, is_horizontal: bool| {
+        character_map.get(ch).is_some_and(|property| {
+            if is_horizontal {
+                property.line_overlap(CellGrid::k(), CellGrid::o())
+            } else {
+                property.line_overlap(CellGrid::c(), CellGrid::w())
+            }
+        })
+    };
+
+    let mut outline: BTreeSet<Cell> = BTreeSet::new();
+    let mut repairs = vec![];
+    let mut expect = |cell: Cell, expected: char| {
+        outline.insert(cell);
+        let is_fine = match (char_at(cell), expected) {
+            (Some(ch), '-') => is_along(ch, true),
+            (Some(ch), '|') => is_along(ch, false),
+            (Some(ch), _) => !ch.is_alphanumeric(),
+            (None, _) => false,
+        };
+        if !is_fine {
+            repairs.push(Repair::new(cell, expected));
+        }
+    };
+    let (top_corner, bottom_corner) =
+        if is_rounded { ('.', '\'') } else { ('+', '+') };
+    for x in left..=right {
+        let (top_ch, bottom_ch) = if x == left || x == right {
+            (top_corner, bottom_corner)
+        } else {
+            ('-', '-')
+        };
+        expect(Cell::new(x, top), top_ch);
+        expect(Cell::new(x, bottom), bottom_ch);
+    }
+    for y in top + 1..bottom {
+        expect(Cell::new(left, y), '|');
+        expect(Cell::new(right, y), '|');
+    }
+    repairs.extend(
+        span.iter()
+            .filter(|(cell, _ch)| !outline.contains(cell))
+            .map(|(cell, _ch)| Repair::new(*cell, ' ')),
+    );
+    repairs.sort();
+    repairs.dedup();
+    repairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{buffer::CellBuffer, Point};
+
+    #[test]
+    fn test_missing_corner() {
+        let art = r#"
+ -----+
+|     |
++-----+
+        "#;
+        let cell_buffer = CellBuffer::from(art);
+        let span: Span = cell_buffer
+            .iter()
+            .map(|(c, ch)| (*c, *ch))
+            .collect::<Vec<_>>()
+            .into();
+        let rect =
+            Rect::new(Point::new(0.5, 3.0), Point::new(6.5, 7.0), false, false);
+        let repairs = rect_repairs(&rect, &span, &CharacterMap::default());
+        assert_eq!(repairs, vec![Repair::new(Cell::new(0, 1), '+')]);
+    }
+
+    #[test]
+    fn test_offset_side() {
+        let art = r#"
++-----+
+|      |
++-----+
+        "#;
+        let cell_buffer = CellBuffer::from(art);
+        let span: Span = cell_buffer
+            .iter()
+            .map(|(c, ch)| (*c, *ch))
+            .collect::<Vec<_>>()
+            .into();
+        let rect =
+            Rect::new(Point::new(0.5, 3.0), Point::new(6.5, 7.0), false, false);
+        let repairs = rect_repairs(&rect, &span, &CharacterMap::default());
+        assert_eq!(
+            repairs,
+            vec![
+                Repair::new(Cell::new(6, 2), '|'),
+                Repair::new(Cell::new(7, 2), ' ')
+            ]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use crate::{
+    buffer::{FragmentSpan, Span},
+    fragment::{dashed_line, DashStyle},
+    Cell,
+};
+use std::collections::BTreeMap;
+
+/// the least number of dashes for a run to be drawn as a line
+const MIN_DASHES: usize = 3;
+
+/// Find the horizontal lines which are drawn with spaced out dashes,
+/// or with dashes alternating with dots, and take them out of the drawing.
+///
+/// ```ignore
+///    - - - -      -.-.-.-
+/// ```
+/// The spaced dashes are drawn as a dashed line, while the dashes with dots
+/// are drawn as a dash-dot line. Each of the dashes has to stand alone in its
+/// column, so the dashes that connect to the lines above or below are left to
+/// the drawing.
+///
+/// returns the lines, along with the cells they are made of
+pub(crate) fn endorse_spaced_lines(
+    cells: &BTreeMap<Cell, char>,
+) -> Vec<FragmentSpan> {
+    let mut accepted = vec![];
+    let starts: Vec<Cell> = cells
+        .iter()
+        .filter(|(cell, ch)| {
+            **ch == '-'
+                && cells.get(&cell.left()).is_none()
+                && cells.get(&cell.left().left()) != Some(&'-')
+        })
+        .map(|(cell, _ch)| *cell)
+        .collect();
+    for start in starts {
+        let run = spaced_run(cells, start, ' ', DashStyle::Dashed)
+            .or_else(|| spaced_run(cells, start, '.', DashStyle::DashDot));
+        if let Some((run, dash)) = run {
+            let first = run[0];
+            let last = run[run.len() - 1];
+            let matched: Vec<(Cell, char)> = run
+                .iter()
+                .filter_map(|cell| cells.get(cell).map(|ch| (*cell, *ch)))
+                .collect();
+            accepted.push(FragmentSpan::new(
+                Span::from(matched),
+                dashed_line(first.k(), last.o(), dash),
+            ));
+        }
+    }
+    accepted
+}
+
+/// the cells of the run starting at `start`, made of dashes separated
+/// by the `gap` character, where a space is an empty cell.
+fn spaced_run(
+    cells: &BTreeMap<Cell, char>,
+    start: Cell,
+    gap: char,
+    dash: DashStyle,
+) -> Option<(Vec<Cell>, DashStyle)> {
+    let is_gap = |cell: &Cell| match cells.get(cell) {
+        None => gap == ' ',
+        Some(ch) => *ch == gap,
+    };
+    let is_alone = |cell: &Cell| {
+        cells.get(&cell.top()).is_none() && cells.get(&cell.bottom()).is_none()
+    };
+    let mut run = vec![];
+    let mut dashes = 0;
+    let mut current = start;
+    loop {
+        if cells.get(&current) != Some(&'-') || !is_alone(&current) {
+            return None;
+        }
+        run.push(current);
+        dashes += 1;
+        let next = current.right();
+        if !is_gap(&next) || cells.get(&next.right()) != Some(&'-') {
+            break;
+        }
+        if gap != ' ' {
+            run.push(next);
+        }
+        current = next.right();
+    }
+    let is_end = cells.get(&current.right()).is_none();
+    (dashes >= MIN_DASHES && is_end).then_some((run, dash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{buffer::CellBuffer, Fragment};
+
+    fn dashes_of(art: &str) -> Vec<DashStyle> {
+        let cell_buffer = CellBuffer::from(art);
+        endorse_spaced_lines(&cell_buffer)
+            .into_iter()
+            .map(|frag_span| match frag_span.fragment {
+                Fragment::Line(line) => line.dash,
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_spaced_lines() {
+        let art = r#"
+    - - - -      -.-.-.-
+            "#;
+        assert_eq!(dashes_of(art), vec![DashStyle::Dashed, DashStyle::DashDot]);
+    }
+
+    #[test]
+    fn test_not_spaced_lines() {
+        let art = r#"
+    a - b - c   - -   ----   -.-   +-+-+   -.-.-.
+
+    - - - -
+      |
+            "#;
+        assert!(dashes_of(art).is_empty());
+    }
+}
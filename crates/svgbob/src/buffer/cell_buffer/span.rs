@@ -1,13 +1,17 @@
 use crate::{
     buffer::{
-        cell_buffer::{Contacts, Endorse},
+        cell_buffer::{endorse, repair, Contacts, Endorse, Repair},
         fragment_buffer::FragmentSpan,
         FragmentBuffer, Property, PropertyBuffer, StringBuffer,
     },
     fragment,
-    fragment::Circle,
-    map::{circle_map, UNICODE_FRAGMENTS},
-    Cell, Fragment, Merge, Point, Settings,
+    fragment::{Circle, Rect},
+    map::{
+        character_map::BUILTIN_CHARACTER_MAP, circle_map, circuit_map,
+        cylinder_map, ending_map, gate_map, mid_marker_map, sequence_map,
+        table_map, UNICODE_FRAGMENTS,
+    },
+    Cell, CharacterMap, Fragment, Merge, Point, Settings,
 };
 use itertools::Itertools;
 use std::{
@@ -131,28 +135,173 @@ impl Span {
     ///
     ///
     /// TODO: return the rejects as Span, instead of Contacts
-    pub(crate) fn endorse(self) -> Endorse<FragmentSpan, Span> {
+    pub(crate) fn endorse(
+        self,
+        settings: &Settings,
+    ) -> Endorse<FragmentSpan, Span> {
+        // the endings attached to the sides of the boxes are taken out
+        // while the boxes are still in the span
+        let (attached_endings, span) =
+            ending_map::endorse_attached_endings(&self);
+        if span.is_empty() {
+            return Endorse {
+                accepted: attached_endings,
+                rejects: vec![],
+                repairs: vec![],
+            };
+        }
         // try to endorse as circles or arcs
-        let (mut accepted, un_endorsed_span): (Vec<FragmentSpan>, Span) =
-            self.endorse_to_arcs_and_circles();
+        let (mut accepted, un_endorsed_span, mut repairs) =
+            match span.endorse_to_tolerant_circle(settings) {
+                Some((circle, un_endorsed_span, repairs)) => {
+                    (vec![circle], un_endorsed_span, repairs)
+                }
+                None => {
+                    let (accepted, un_endorsed_span) =
+                        span.endorse_to_arcs_and_circles();
+                    (accepted, un_endorsed_span, vec![])
+                }
+            };
+        accepted.extend(attached_endings);
 
         // convert into contacts and try to endorse as rects fragments
-        let un_endorsed_contacts: Vec<Contacts> = un_endorsed_span.into();
-        let rect_endorsed: Endorse<FragmentSpan, Contacts> =
+        let un_endorsed_contacts: Vec<Contacts> =
+            un_endorsed_span.into_contacts(&settings.character_map);
+        let mut rect_endorsed: Endorse<FragmentSpan, Contacts> =
             Contacts::endorse_rects(un_endorsed_contacts);
+        if settings.tolerant_shapes {
+            rect_endorsed =
+                Self::endorse_tolerant_rects(rect_endorsed, settings);
+        }
 
-        accepted.extend(rect_endorsed.accepted);
+        accepted.extend(rect_endorsed.accepted.into_iter().map(
+            |fragment_span| Self::with_corner_radius(fragment_span, settings),
+        ));
+        repairs.extend(rect_endorsed.repairs);
 
         let re_endorsed = Self::re_endorse(rect_endorsed.rejects);
 
         let mut endorsed = Endorse {
             accepted,
             rejects: vec![],
+            repairs,
         };
         endorsed.extend(re_endorsed);
         endorsed
     }
 
+    /// endorse this span as the circle it is meant to be, when it is almost
+    /// a circle but not quite, returning the repairs to its cells
+    fn endorse_to_tolerant_circle(
+        &self,
+        settings: &Settings,
+    ) -> Option<(FragmentSpan, Span, Vec<Repair>)> {
+        if !settings.tolerant_shapes
+            || cylinder_map::endorse_cylinder_span(self).is_some()
+            || circle_map::endorse_circle_span(self).is_some()
+        {
+            return None;
+        }
+        let (circle, un_endorsed_span, repairs) =
+            circle_map::endorse_tolerant_circle_span(self)?;
+        let circle_frag_span = FragmentSpan::new(self.clone(), circle.into());
+        Some((circle_frag_span, un_endorsed_span, repairs))
+    }
+
+    /// endorse the rejected lines and arcs together as a rect, when they
+    /// almost form one but have small gaps or misalignments.
+    /// The repairs to the cells are recorded along with the rect.
+    fn endorse_tolerant_rects(
+        endorsed: Endorse<FragmentSpan, Contacts>,
+        settings: &Settings,
+    ) -> Endorse<FragmentSpan, Contacts> {
+        let Endorse {
+            mut accepted,
+            rejects,
+            mut repairs,
+        } = endorsed;
+        let is_stroke = |contacts: &Contacts| {
+            contacts.fragments().iter().all(|fragment| {
+                fragment.as_line().is_some() || fragment.as_arc().is_some()
+            })
+        };
+        let fragments: Vec<&Fragment> = rejects
+            .iter()
+            .filter(|contacts| is_stroke(contacts))
+            .flat_map(|contacts| contacts.fragments())
+            .collect();
+        let rect = endorse::endorse_tolerant_rect(&fragments)
+            .or_else(|| endorse::endorse_tolerant_rounded_rect(&fragments));
+        let Some(rect) = rect else {
+            return Endorse {
+                accepted,
+                rejects,
+                repairs,
+            };
+        };
+        let (strokes, rejects): (Vec<Contacts>, Vec<Contacts>) =
+            rejects.into_iter().partition(is_stroke);
+        let span: Span = strokes
+            .iter()
+            .flat_map(|contacts| contacts.span().0)
+            .collect::<Vec<_>>()
+            .into();
+        repairs.extend(repair::rect_repairs(
+            &rect,
+            &span,
+            &settings.character_map,
+        ));
+        accepted.push(FragmentSpan::new(span, rect.into()));
+        Endorse {
+            accepted,
+            rejects,
+            repairs,
+        }
+    }
+
+    /// apply the corner radius in the settings to the endorsed rects,
+    /// the `+` corners are rounded only when all 4 of the corners are `+`
+    fn with_corner_radius(
+        fragment_span: FragmentSpan,
+        settings: &Settings,
+    ) -> FragmentSpan {
+        let FragmentSpan { span, fragment } = fragment_span;
+        let fragment = match fragment {
+            Fragment::Rect(rect)
+                if rect.radius.is_some_and(|radius| radius > 0.0) =>
+            {
+                Fragment::Rect(Rect {
+                    radius: settings.rounded_corner_radius.or(rect.radius),
+                    ..rect
+                })
+            }
+            Fragment::Rect(rect) if span.is_plus_cornered() => {
+                Fragment::Rect(Rect {
+                    radius: settings.plus_corner_radius,
+                    ..rect
+                })
+            }
+            fragment => fragment,
+        };
+        FragmentSpan::new(span, fragment)
+    }
+
+    /// the 4 corners of the bounds of this span are `+`
+    fn is_plus_cornered(&self) -> bool {
+        self.bounds().is_some_and(|(top_left, bottom_right)| {
+            [
+                top_left,
+                Cell::new(bottom_right.x, top_left.y),
+                Cell::new(top_left.x, bottom_right.y),
+                bottom_right,
+            ]
+            .iter()
+            .all(|corner| {
+                self.iter().any(|(cell, ch)| cell == corner && *ch == '+')
+            })
+        })
+    }
+
     /// re try endorsing the contacts into arc and circles by converting it to span first
     fn re_endorse(rect_rejects: Vec<Contacts>) -> Endorse<FragmentSpan, Span> {
         // convert back to span
@@ -173,14 +322,23 @@ impl Span {
         Endorse {
             accepted: accepted.into_iter().flatten().collect(),
             rejects,
+            repairs: vec![],
         }
     }
 
-    /// endorse this span into circles, half_circle, quarter_circle only
+    /// endorse this span into cylinders, circles, half_circle, quarter_circle only
     fn endorse_to_arcs_and_circles(self) -> (Vec<FragmentSpan>, Span) {
         let mut accepted = vec![];
         let (top_left, _) = self.bounds().expect("must have bounds");
-        let un_endorsed_span: Span = if let Some((circle, un_endorsed_span)) =
+        let un_endorsed_span: Span = if let Some((cylinder, un_endorsed_span)) =
+            cylinder_map::endorse_cylinder_span(&self)
+        {
+            let cylinder = cylinder.absolute_position(top_left);
+            let cylinder_frag_span =
+                FragmentSpan::new(self.clone(), cylinder.into());
+            accepted.push(cylinder_frag_span);
+            un_endorsed_span
+        } else if let Some((circle, un_endorsed_span)) =
             circle_map::endorse_circle_span(&self)
         {
             let circle = circle.absolute_position(top_left);
@@ -218,6 +376,86 @@ impl Span {
         (accepted, un_endorsed_span)
     }
 
+    /// create a property buffer for all the cells of this span
+    /// using the properties of the chars from the character map
+    pub fn property_buffer<'p>(
+        &self,
+        character_map: &'p CharacterMap,
+    ) -> PropertyBuffer<'p> {
+        let mut pb = PropertyBuffer::new();
+        for (cell, ch) in self.iter() {
+            if let Some(property) = character_map.get(*ch) {
+                pb.as_mut().insert(*cell, property);
+            }
+        }
+        pb
+    }
+
+    /// First we crate a property buffer based on the cell,char content of this span
+    /// and then based on the property, we extract the accurate fragments
+    ///
+    /// If a character has no property, try to see if has equivalent fragments from unicode_map
+    /// otherwise add it to the fragment_buffer as a text fragment
+    pub fn into_fragment_buffer(
+        self,
+        character_map: &CharacterMap,
+    ) -> FragmentBuffer {
+        // the tables, the sequence diagrams, the logic gates, the circuit
+        // symbols, the arrowheads in the middle of the lines and the line
+        // endings are taken out first, since they are made of characters which
+        // has their own meaning when taken individually
+        let (tables, span) = table_map::endorse_tables(&self);
+        let (sequences, span) = sequence_map::endorse_sequences(&span);
+        let (gates, span) = gate_map::endorse_gates(&span);
+        let (components, span) = circuit_map::endorse_circuits(&span);
+        let (mid_markers, span) = mid_marker_map::endorse_mid_markers(&span);
+        let (endings, span) = ending_map::endorse_endings(&span);
+        let pb = span.property_buffer(character_map);
+        let mut fb = FragmentBuffer::from(pb.clone());
+        for (cell, frag_span) in tables
+            .into_iter()
+            .chain(sequences)
+            .chain(gates)
+            .chain(components)
+            .chain(mid_markers)
+            .chain(endings)
+        {
+            fb.add_fragment_span_to_cell(cell, frag_span);
+        }
+        for (cell, ch) in span.iter() {
+            if pb.as_ref().get(cell).is_none() {
+                if let Some(fragments) = UNICODE_FRAGMENTS.get(ch) {
+                    fb.add_fragments_to_cell(*cell, *ch, fragments.clone());
+                } else {
+                    fb.add_fragment_to_cell(
+                        *cell,
+                        *ch,
+                        fragment::cell_text(*ch),
+                    );
+                }
+            }
+        }
+        fb
+    }
+
+    /// Grouping cell by adjacents are not enough
+    ///
+    /// grouping them together when they are actually connected
+    /// is the most approprivate way of grouping
+    /// Span just provides an optimization of the number
+    /// of elements to be checked.
+    /// Only elements on the same span are checked to see if they
+    /// belong on the same group
+    pub fn into_contacts(self, character_map: &CharacterMap) -> Vec<Contacts> {
+        let fb = self.into_fragment_buffer(character_map);
+        let merged_fragments: Vec<FragmentSpan> = fb.merge_fragment_spans();
+        let contacts: Vec<Contacts> = merged_fragments
+            .into_iter()
+            .map(|frag| Contacts::new(frag))
+            .collect();
+        Contacts::merge_recursive(contacts)
+    }
+
     /// create a span of the cells that is inside of the start and end bound cells
     pub(crate) fn extract(&self, bound1: Cell, bound2: Cell) -> Self {
         Span(
@@ -284,62 +522,25 @@ impl Bounds {
 }
 
 /// create a property buffer for all the cells of this span
+/// using only the built-in characters
 impl<'p> From<Span> for PropertyBuffer<'p> {
     fn from(span: Span) -> Self {
-        let mut pb = PropertyBuffer::new();
-        for (cell, ch) in span.iter() {
-            if let Some(property) = Property::from_char(*ch) {
-                pb.as_mut().insert(*cell, property);
-            }
-        }
-        pb
+        span.property_buffer(&BUILTIN_CHARACTER_MAP)
     }
 }
 
-/// Grouping cell by adjacents are not enough
-///
-/// grouping them together when they are actually connected
-/// is the most approprivate way of grouping
-/// Span just provides an optimization of the number
-/// of elements to be checked.
-/// Only elements on the same span are checked to see if they
-/// belong on the same group
-///
+/// group the cells of this span by their contacts,
+/// using only the built-in characters
 impl From<Span> for Vec<Contacts> {
     fn from(span: Span) -> Vec<Contacts> {
-        let fb = FragmentBuffer::from(span);
-        let merged_fragments: Vec<FragmentSpan> = fb.merge_fragment_spans();
-        let contacts: Vec<Contacts> = merged_fragments
-            .into_iter()
-            .map(|frag| Contacts::new(frag))
-            .collect();
-        Contacts::merge_recursive(contacts)
+        span.into_contacts(&BUILTIN_CHARACTER_MAP)
     }
 }
 
-/// First we crate a property buffer based on the cell,char content of this span
-/// and then based on the property, we extract the accurate fragments
-///
-/// If a character has no property, try to see if has equivalent fragments from unicode_map
-/// otherwise add it to the fragment_buffer as a text fragment
+/// the fragments of this span, using only the built-in characters
 impl From<Span> for FragmentBuffer {
     fn from(span: Span) -> FragmentBuffer {
-        let pb = PropertyBuffer::from(span.clone());
-        let mut fb = FragmentBuffer::from(pb.clone());
-        for (cell, ch) in span.iter() {
-            if pb.as_ref().get(cell).is_none() {
-                if let Some(fragments) = UNICODE_FRAGMENTS.get(ch) {
-                    fb.add_fragments_to_cell(*cell, *ch, fragments.clone());
-                } else {
-                    fb.add_fragment_to_cell(
-                        *cell,
-                        *ch,
-                        fragment::cell_text(*ch),
-                    );
-                }
-            }
-        }
-        fb
+        span.into_fragment_buffer(&BUILTIN_CHARACTER_MAP)
     }
 }
 
@@ -188,7 +188,8 @@ fn test_endorse_circle() {
     let Endorse {
         accepted: mut fragments,
         rejects: _groups,
-    } = span.endorse();
+        ..
+    } = span.endorse(&Settings::default());
     for (i, frag) in fragments.iter().enumerate() {
         println!("frag {}:\n{}", i, frag);
     }
@@ -222,7 +223,8 @@ fn test_endorse_circle_with_rect() {
     let Endorse {
         accepted: mut fragments,
         rejects: _groups,
-    } = span1.endorse();
+        ..
+    } = span1.endorse(&Settings::default());
     assert_eq!(fragments.len(), 2);
 
     let circle = fragments.remove(0);
@@ -274,7 +276,8 @@ fn test_endorse_with_big_circle() {
     let Endorse {
         accepted: mut fragments,
         rejects: _groups,
-    } = span1.endorse();
+        ..
+    } = span1.endorse(&Settings::default());
     assert_eq!(fragments.len(), 1);
 
     let circle = fragments.remove(0);
@@ -313,7 +316,8 @@ fn test_endorse_with_big_circle_extra_match() {
     let Endorse {
         accepted: fragments,
         rejects: groups,
-    } = span1.endorse();
+        ..
+    } = span1.endorse(&Settings::default());
     assert_eq!(fragments.len(), 1);
     assert_eq!(groups.len(), 0);
     for (i, fragment) in groups.iter().enumerate() {
@@ -369,7 +373,8 @@ fn test_endorse_arc() {
     let Endorse {
         accepted: mut fragments,
         rejects: _groups,
-    } = span.endorse();
+        ..
+    } = span.endorse(&Settings::default());
     for (i, frag) in fragments.iter().enumerate() {
         println!("frag {}:\n{}", i, frag);
     }
@@ -0,0 +1,291 @@
+use crate::{
+    buffer::{CellGrid, Span},
+    fragment::CellText,
+    map::is_horizontal_line,
+    Cell, FragmentSpan, Point, Settings,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Find the words in the cells and take them out of the drawing.
+///
+/// A word is a run of characters in a row which is mostly letters and digits,
+/// such as `I/O`, `x86_64`, `it's` or `greet()`. The punctuation embedded in
+/// a word would otherwise be drawn as lines, arcs and markers.
+/// The share of letters and digits needed is set by `text_strictness`
+/// in the settings, and a word must have at least 2 of them, so a lone
+/// `o`, `x` or `v` followed by a punctuation is still drawn.
+///
+/// A word which is connected to the strokes of the drawing in the rows
+/// above or below it is left to the drawing.
+///
+/// ```ignore
+///    I/O      +----+
+///             |name|
+///             +----+
+///               |
+///               v.2
+/// ```
+/// `I/O` is a word, while `|name|` and `v.2` are left to the drawing
+/// since they are connected to the lines above them.
+///
+/// The line characters `|`, `-`, `+` and `=` are never part of a word,
+/// so `o-o` and the columns of a table such as `| x.y|true |` are still drawn.
+/// An arrowhead or marker at the end of a word which touches a line on its
+/// side, such as the `>` in `client--->server`, is left to the drawing too.
+///
+/// A word directly above a horizontal arrow is the label of the arrow,
+/// and is left to the drawing so it can be matched along with it,
+/// such as the message labels in a sequence diagram.
+///
+/// returns the words as text, along with the cells they are made of
+pub(crate) fn endorse_words(
+    cells: &BTreeMap<Cell, char>,
+    settings: &Settings,
+) -> Vec<FragmentSpan> {
+    let words: Vec<Vec<(Cell, char)>> = runs(cells)
+        .into_iter()
+        .map(|run| trim_markers(cells, run))
+        .filter(|run| is_word(run, settings.text_strictness))
+        .collect();
+    let word_cells: BTreeSet<Cell> = words
+        .iter()
+        .flat_map(|word| word.iter().map(|(cell, _ch)| *cell))
+        .collect();
+
+    let words: Vec<Vec<(Cell, char)>> = words
+        .into_iter()
+        .filter(|word| {
+            !word.iter().any(|(cell, _ch)| {
+                is_connected(cells, &word_cells, *cell, settings)
+                    || is_above_arrow(cells, *cell)
+            })
+        })
+        .collect();
+
+    words
+        .into_iter()
+        .map(|word| {
+            let start = word[0].0;
+            let content: String = word.iter().map(|(_cell, ch)| *ch).collect();
+            FragmentSpan::new(
+                Span::from(word),
+                CellText::new(start, content).into(),
+            )
+        })
+        .collect()
+}
+
+/// the characters that are always left to the drawing
+fn is_line_char(ch: char) -> bool {
+    matches!(ch, '|' | '-' | '+' | '=')
+}
+
+/// the runs of characters in each row which are not separated by whitespace
+/// or by line characters
+fn runs(cells: &BTreeMap<Cell, char>) -> Vec<Vec<(Cell, char)>> {
+    let mut sorted: Vec<(Cell, char)> = cells
+        .iter()
+        .filter(|(_cell, ch)| !is_line_char(**ch))
+        .map(|(cell, ch)| (*cell, *ch))
+        .collect();
+    sorted.sort_by_key(|(cell, _ch)| (cell.y, cell.x));
+
+    let mut runs: Vec<Vec<(Cell, char)>> = vec![];
+    for (cell, ch) in sorted {
+        match runs.last_mut() {
+            Some(run)
+                if run.last().is_some_and(|(last, _)| {
+                    last.y == cell.y && last.x + 1 == cell.x
+                }) =>
+            {
+                run.push((cell, ch))
+            }
+            _ => runs.push(vec![(cell, ch)]),
+        }
+    }
+    runs
+}
+
+/// the markers which can be at the end of a line
+fn is_marker_char(ch: char) -> bool {
+    matches!(ch, '<' | '>' | '^' | 'v' | 'o' | '*' | '.' | '\'')
+}
+
+/// take out the marker at each end of the run which touches
+/// a line character on its outer side
+fn trim_markers(
+    cells: &BTreeMap<Cell, char>,
+    mut run: Vec<(Cell, char)>,
+) -> Vec<(Cell, char)> {
+    let touches_line =
+        |cell: Cell| cells.get(&cell).is_some_and(|ch| is_line_char(*ch));
+    if run.last().is_some_and(|(cell, ch)| {
+        is_marker_char(*ch) && touches_line(cell.right())
+    }) {
+        run.pop();
+    }
+    if run.first().is_some_and(|(cell, ch)| {
+        is_marker_char(*ch) && touches_line(cell.left())
+    }) {
+        run.remove(0);
+    }
+    run
+}
+
+/// a run is a word when the share of letters and digits in it
+/// is above the strictness.
+/// Runs of only letters and digits are already rendered as text, and are
+/// left in the cells so they can be matched along with the drawing around them
+fn is_word(run: &[(Cell, char)], strictness: f32) -> bool {
+    let alphanumerics = run
+        .iter()
+        .filter(|(_cell, ch)| ch.is_alphanumeric())
+        .count();
+    alphanumerics >= 2
+        && alphanumerics < run.len()
+        && alphanumerics as f32 / run.len() as f32 > strictness
+}
+
+/// check if the character at `cell` is connected to a drawing character
+/// in the rows above or below it, which is not part of any word.
+fn is_connected(
+    cells: &BTreeMap<Cell, char>,
+    word_cells: &BTreeSet<Cell>,
+    cell: Cell,
+    settings: &Settings,
+) -> bool {
+    // the neighbor and the line from its center that would reach this cell
+    let neighbors: [(Cell, Point, Point); 6] = [
+        (cell.top_left(), CellGrid::m(), CellGrid::y()),
+        (cell.top(), CellGrid::m(), CellGrid::w()),
+        (cell.top_right(), CellGrid::m(), CellGrid::u()),
+        (cell.bottom_left(), CellGrid::m(), CellGrid::e()),
+        (cell.bottom(), CellGrid::m(), CellGrid::c()),
+        (cell.bottom_right(), CellGrid::m(), CellGrid::a()),
+    ];
+    neighbors.iter().any(|(neighbor, a, b)| {
+        if word_cells.contains(neighbor) {
+            return false;
+        }
+        cells
+            .get(neighbor)
+            .and_then(|ch| settings.character_map.get(*ch))
+            .is_some_and(|property| property.line_overlap(*a, *b))
+    })
+}
+
+/// check if the cell directly below `cell` is part of a horizontal line
+/// which ends with an arrowhead on either side
+fn is_above_arrow(cells: &BTreeMap<Cell, char>, cell: Cell) -> bool {
+    let below = cell.bottom();
+    if !is_horizontal_line(cells.get(&below)) {
+        return false;
+    }
+    let is_run_char = |cell: &Cell| {
+        is_horizontal_line(cells.get(cell)) || cells.get(cell) == Some(&'+')
+    };
+    let mut left = below;
+    while is_run_char(&left) {
+        left = left.left();
+    }
+    let mut right = below;
+    while is_run_char(&right) {
+        right = right.right();
+    }
+    cells.get(&left) == Some(&'<') || cells.get(&right) == Some(&'>')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::CellBuffer;
+
+    fn words_of(art: &str, settings: &Settings) -> Vec<String> {
+        let cell_buffer = CellBuffer::from(art);
+        endorse_words(&cell_buffer, settings)
+            .into_iter()
+            .map(|frag_span| {
+                frag_span.fragment.as_cell_text().unwrap().content.clone()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ambiguous_words() {
+        let art = "I/O v2 x86_64 a_b it's foo_bar_baz wait... greet()";
+        let words = words_of(art, &Settings::default());
+        assert_eq!(
+            words,
+            vec![
+                "I/O",
+                "x86_64",
+                "a_b",
+                "it's",
+                "foo_bar_baz",
+                "wait...",
+                "greet()"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_drawings_are_not_words() {
+        let art = r#"
+    o----o   +--v--+   *-->   x   A--B   o-o   o=o
+            "#;
+        let words = words_of(art, &Settings::default());
+        assert!(words.is_empty());
+
+        // the column lines of the table are left to the drawing
+        let art = r#"
+    +-----+-----+
+    | x.y|true  |
+    +-----+-----+
+            "#;
+        let words = words_of(art, &Settings::default());
+        assert_eq!(words, vec!["x.y"]);
+    }
+
+    #[test]
+    fn test_connected_word_is_left_to_the_drawing() {
+        let art = r#"
+    +----+
+    |name|
+    +----+
+      |
+      v.2
+            "#;
+        let words = words_of(art, &Settings::default());
+        assert!(words.is_empty());
+    }
+
+    #[test]
+    fn test_arrowheads_next_to_words_are_left_to_the_drawing() {
+        let art = "client---->server   Alice<----Bob   get_x()-->a.b";
+        let words = words_of(art, &Settings::default());
+        assert_eq!(words, vec!["get_x()", "a.b"]);
+
+        let art = r#"
+      get_user()
+    +------------>
+            "#;
+        let words = words_of(art, &Settings::default());
+        assert!(words.is_empty());
+    }
+
+    #[test]
+    fn test_strictness() {
+        let art = "wait... I/O x86_64";
+        let settings = Settings {
+            text_strictness: 0.75,
+            ..Settings::default()
+        };
+        assert_eq!(words_of(art, &settings), vec!["x86_64"]);
+
+        let settings = Settings {
+            text_strictness: 1.0,
+            ..Settings::default()
+        };
+        assert!(words_of(art, &settings).is_empty());
+    }
+}
@@ -104,7 +104,7 @@ impl FragmentBuffer {
     /// Note: Same fragment span can be stored in the same cell
     /// as it simplifies the algorithm for mergin marker line (lines with dots, and arrows)
     /// Since they will be attached to each other at the cell level
-    fn add_fragment_span_to_cell(
+    pub(crate) fn add_fragment_span_to_cell(
         &mut self,
         cell: Cell,
         fragment_span: FragmentSpan,
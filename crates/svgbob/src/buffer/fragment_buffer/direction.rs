@@ -1,4 +1,4 @@
-use crate::buffer::CellGrid;
+use crate::{buffer::CellGrid, Point};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Direction {
@@ -39,4 +39,20 @@ impl Direction {
             Direction::Top | Direction::Bottom => CellGrid::height(),
         }
     }
+
+    /// the vector pointing to this direction, the diagonals goes from
+    /// corner to corner of the cell
+    pub(crate) fn vector(&self) -> Point {
+        let (w, h) = (CellGrid::width(), CellGrid::height());
+        match self {
+            Direction::TopLeft => Point::new(-w, -h),
+            Direction::Top => Point::new(0.0, -h),
+            Direction::TopRight => Point::new(w, -h),
+            Direction::Left => Point::new(-w, 0.0),
+            Direction::Right => Point::new(w, 0.0),
+            Direction::BottomLeft => Point::new(-w, h),
+            Direction::Bottom => Point::new(0.0, h),
+            Direction::BottomRight => Point::new(w, h),
+        }
+    }
 }
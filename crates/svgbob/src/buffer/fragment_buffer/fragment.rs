@@ -1,9 +1,13 @@
 use crate::{buffer::Cell, map::unicode_map::FRAGMENTS_UNICODE, Merge, Point};
 pub use crate::{Property, Settings, Signal};
 pub use arc::Arc;
+pub use chain::{Chain, ChainLink};
 pub use circle::Circle;
-pub use line::Line;
-pub use marker_line::{Marker, MarkerLine};
+pub use cylinder::Cylinder;
+pub use gate::{Gate, GateKind};
+pub use isometric_box::{Face, IsometricBox};
+pub use line::{DashStyle, Line};
+pub use marker_line::{Marker, MarkerLine, MidMarker};
 use parry2d::{
     bounding_volume::{Aabb, BoundingVolume},
     math::Isometry,
@@ -11,17 +15,27 @@ use parry2d::{
     shape::{ConvexPolygon, Polyline, Segment, Shape},
 };
 pub use polygon::{Polygon, PolygonTag};
-pub use rect::Rect;
+pub use rect::{Rect, Shade};
 use sauron::Node;
+pub use sequence::{Lifeline, Message};
+pub use snippet::Snippet;
 use std::{cmp::Ordering, fmt};
+pub use table::Table;
 pub use text::{CellText, Text};
 
 mod arc;
+mod chain;
 mod circle;
+mod cylinder;
+mod gate;
+mod isometric_box;
 mod line;
 mod marker_line;
 mod polygon;
 mod rect;
+mod sequence;
+mod snippet;
+mod table;
 mod text;
 
 /// ```ignore
@@ -41,10 +55,18 @@ mod text;
 pub enum Fragment {
     Line(Line),
     MarkerLine(MarkerLine),
+    Chain(Chain),
     Circle(Circle),
     Arc(Arc),
     Polygon(Polygon),
     Rect(Rect),
+    Cylinder(Cylinder),
+    Gate(Gate),
+    Table(Table),
+    IsometricBox(IsometricBox),
+    Lifeline(Lifeline),
+    Message(Message),
+    Snippet(Snippet),
     // cell base
     CellText(CellText),
     // point base
@@ -80,6 +102,7 @@ impl Fragment {
     pub(crate) fn line_overlap(&self, a: Point, b: Point) -> bool {
         match self {
             Fragment::Line(line) => line.overlaps(a, b),
+            Fragment::MarkerLine(mline) => mline.line.overlaps(a, b),
             _ => false,
         }
     }
@@ -88,6 +111,7 @@ impl Fragment {
     pub(crate) fn has_endpoint(&self, p: Point) -> bool {
         match self {
             Fragment::Line(line) => line.has_endpoint(p),
+            Fragment::MarkerLine(mline) => mline.line.has_endpoint(p),
             Fragment::Arc(arc) => arc.has_endpoint(p),
             _ => false,
         }
@@ -232,6 +256,30 @@ impl Fragment {
             Fragment::Rect(rect) => {
                 Fragment::Rect(rect.absolute_position(cell))
             }
+            Fragment::Cylinder(cylinder) => {
+                Fragment::Cylinder(cylinder.absolute_position(cell))
+            }
+            Fragment::Gate(gate) => {
+                Fragment::Gate(gate.absolute_position(cell))
+            }
+            Fragment::Table(table) => {
+                Fragment::Table(table.absolute_position(cell))
+            }
+            Fragment::IsometricBox(isometric_box) => {
+                Fragment::IsometricBox(isometric_box.absolute_position(cell))
+            }
+            Fragment::Chain(chain) => {
+                Fragment::Chain(chain.absolute_position(cell))
+            }
+            Fragment::Lifeline(lifeline) => {
+                Fragment::Lifeline(lifeline.absolute_position(cell))
+            }
+            Fragment::Message(message) => {
+                Fragment::Message(message.absolute_position(cell))
+            }
+            Fragment::Snippet(snippet) => {
+                Fragment::Snippet(snippet.absolute_position(cell))
+            }
             Fragment::Text(text) => {
                 Fragment::Text(text.absolute_position(cell))
             }
@@ -254,6 +302,24 @@ impl Fragment {
                 Fragment::Polygon(polygon.scale(scale))
             }
             Fragment::Rect(rect) => Fragment::Rect(rect.scale(scale)),
+            Fragment::Cylinder(cylinder) => {
+                Fragment::Cylinder(cylinder.scale(scale))
+            }
+            Fragment::Gate(gate) => Fragment::Gate(gate.scale(scale)),
+            Fragment::Table(table) => Fragment::Table(table.scale(scale)),
+            Fragment::IsometricBox(isometric_box) => {
+                Fragment::IsometricBox(isometric_box.scale(scale))
+            }
+            Fragment::Chain(chain) => Fragment::Chain(chain.scale(scale)),
+            Fragment::Lifeline(lifeline) => {
+                Fragment::Lifeline(lifeline.scale(scale))
+            }
+            Fragment::Message(message) => {
+                Fragment::Message(message.scale(scale))
+            }
+            Fragment::Snippet(snippet) => {
+                Fragment::Snippet(snippet.scale(scale))
+            }
             Fragment::Text(text) => Fragment::Text(text.scale(scale)),
             // the CellText is converted into text fragment first, then scaled
             Fragment::CellText(ctext) => {
@@ -272,6 +338,20 @@ impl Fragment {
             Fragment::Arc(arc) => Fragment::Arc(arc.clone()),
             Fragment::Polygon(polygon) => Fragment::Polygon(polygon.clone()),
             Fragment::Rect(rect) => Fragment::Rect(rect.clone()),
+            Fragment::Cylinder(cylinder) => {
+                Fragment::Cylinder(cylinder.clone())
+            }
+            Fragment::Gate(gate) => Fragment::Gate(gate.clone()),
+            Fragment::Table(table) => Fragment::Table(table.clone()),
+            Fragment::IsometricBox(isometric_box) => {
+                Fragment::IsometricBox(isometric_box.clone())
+            }
+            Fragment::Chain(chain) => Fragment::Chain(chain.clone()),
+            Fragment::Lifeline(lifeline) => {
+                Fragment::Lifeline(lifeline.clone())
+            }
+            Fragment::Message(message) => Fragment::Message(message.clone()),
+            Fragment::Snippet(snippet) => Fragment::Snippet(snippet.clone()),
             Fragment::Text(text) => Fragment::Text(text.clone()),
             // the CellText is converted into text fragment first, then scaled
             Fragment::CellText(ctext) => {
@@ -287,10 +367,18 @@ impl Fragment {
         match self {
             Fragment::Line(_) => 10,
             Fragment::MarkerLine(_) => 20,
+            Fragment::Chain(_) => 25,
             Fragment::Circle(_) => 30,
             Fragment::Arc(_) => 40,
             Fragment::Polygon { .. } => 50,
             Fragment::Rect(_) => 60,
+            Fragment::Cylinder(_) => 65,
+            Fragment::Gate(_) => 66,
+            Fragment::Snippet(_) => 67,
+            Fragment::Table(_) => 68,
+            Fragment::IsometricBox(_) => 69,
+            Fragment::Lifeline(_) => 62,
+            Fragment::Message(_) => 63,
             Fragment::Text(_) => 70,
             Fragment::CellText(_) => 80,
         }
@@ -303,6 +391,13 @@ impl Fragment {
         }
     }
 
+    pub fn as_marker_line(&self) -> Option<&MarkerLine> {
+        match self {
+            Fragment::MarkerLine(ref mline) => Some(mline),
+            _ => None,
+        }
+    }
+
     pub fn as_rect(&self) -> Option<&Rect> {
         match self {
             Fragment::Rect(ref rect) => Some(rect),
@@ -310,6 +405,34 @@ impl Fragment {
         }
     }
 
+    pub fn as_table(&self) -> Option<&Table> {
+        match self {
+            Fragment::Table(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    pub fn as_isometric_box(&self) -> Option<&IsometricBox> {
+        match self {
+            Fragment::IsometricBox(isometric_box) => Some(isometric_box),
+            _ => None,
+        }
+    }
+
+    pub fn as_cylinder(&self) -> Option<&Cylinder> {
+        match self {
+            Fragment::Cylinder(cylinder) => Some(cylinder),
+            _ => None,
+        }
+    }
+
+    pub fn as_snippet(&self) -> Option<&Snippet> {
+        match self {
+            Fragment::Snippet(snippet) => Some(snippet),
+            _ => None,
+        }
+    }
+
     pub fn as_polygon(&self) -> Option<&Polygon> {
         match self {
             Fragment::Polygon(polygon) => Some(polygon),
@@ -376,6 +499,19 @@ impl Fragment {
         matches!(self, Fragment::Rect(_))
     }
 
+    pub fn is_cylinder(&self) -> bool {
+        matches!(self, Fragment::Cylinder(_))
+    }
+
+    /// lifelines and messages of a sequence diagram
+    pub fn is_sequence(&self) -> bool {
+        matches!(self, Fragment::Lifeline(_) | Fragment::Message(_))
+    }
+
+    pub fn is_snippet(&self) -> bool {
+        matches!(self, Fragment::Snippet(_))
+    }
+
     pub fn is_text(&self) -> bool {
         matches!(self, Fragment::Text(_))
     }
@@ -391,6 +527,24 @@ impl Fragment {
             _ => false,
         }
     }
+
+    /// the dash style of the stroke of lines and rects
+    pub fn dash(&self) -> DashStyle {
+        match self {
+            Fragment::Line(line) => line.dash,
+            Fragment::MarkerLine(mline) => mline.line.dash,
+            Fragment::Rect(rect) => rect.dash,
+            _ => DashStyle::Solid,
+        }
+    }
+
+    pub fn is_heavy(&self) -> bool {
+        match self {
+            Fragment::Line(line) => line.is_heavy(),
+            Fragment::Rect(rect) => rect.is_heavy(),
+            _ => false,
+        }
+    }
 }
 
 impl Merge for Fragment {
@@ -416,9 +570,17 @@ impl Merge for Fragment {
             }
 
             // line and marker_line
-            (Fragment::Line(line), Fragment::MarkerLine(mline)) => None,
+            (Fragment::Line(line), Fragment::MarkerLine(mline)) => {
+                mline.merge_line(line).map(Fragment::MarkerLine)
+            }
             // marker_line and line
-            (Fragment::MarkerLine(mline), Fragment::Line(line)) => None,
+            (Fragment::MarkerLine(mline), Fragment::Line(line)) => {
+                mline.merge_line(line).map(Fragment::MarkerLine)
+            }
+            // marker_line and marker_line
+            (Fragment::MarkerLine(mline), Fragment::MarkerLine(other)) => {
+                mline.merge(other).map(Fragment::MarkerLine)
+            }
             (Fragment::MarkerLine(mline), Fragment::Polygon(polygon)) => {
                 //mline.merge_polygon(polygon)
                 None
@@ -432,6 +594,10 @@ impl Merge for Fragment {
             (Fragment::Circle(circle), Fragment::Line(line)) => {
                 line.merge_circle(circle)
             }
+            // rect and rect
+            (Fragment::Rect(rect), Fragment::Rect(other_rect)) => {
+                rect.merge(other_rect).map(Fragment::Rect)
+            }
             // cell_text and cell_text
             (Fragment::CellText(ctext), Fragment::CellText(other_ctext)) => {
                 ctext.merge(other_ctext).map(Fragment::CellText)
@@ -451,6 +617,14 @@ impl Bounds for Fragment {
             Fragment::Arc(arc) => arc.bounds(),
             Fragment::Polygon(polygon) => polygon.bounds(),
             Fragment::Rect(rect) => rect.bounds(),
+            Fragment::Cylinder(cylinder) => cylinder.bounds(),
+            Fragment::Gate(gate) => gate.bounds(),
+            Fragment::Table(table) => table.bounds(),
+            Fragment::IsometricBox(isometric_box) => isometric_box.bounds(),
+            Fragment::Chain(chain) => chain.bounds(),
+            Fragment::Lifeline(lifeline) => lifeline.bounds(),
+            Fragment::Message(message) => message.bounds(),
+            Fragment::Snippet(snippet) => snippet.bounds(),
             Fragment::Text(text) => text.bounds(),
             Fragment::CellText(ctext) => ctext.bounds(),
         }
@@ -466,6 +640,14 @@ impl<MSG> From<Fragment> for Node<MSG> {
             Fragment::Arc(arc) => arc.into(),
             Fragment::Polygon(polygon) => polygon.into(),
             Fragment::Rect(rect) => rect.into(),
+            Fragment::Cylinder(cylinder) => cylinder.into(),
+            Fragment::Gate(gate) => gate.into(),
+            Fragment::Table(table) => table.into(),
+            Fragment::IsometricBox(isometric_box) => isometric_box.into(),
+            Fragment::Chain(chain) => chain.into(),
+            Fragment::Lifeline(lifeline) => lifeline.into(),
+            Fragment::Message(message) => message.into(),
+            Fragment::Snippet(snippet) => snippet.into(),
             Fragment::Text(text) => text.into(),
             Fragment::CellText(ctext) => ctext.into(),
         }
@@ -481,6 +663,16 @@ impl fmt::Display for Fragment {
             Fragment::Arc(arc) => write!(f, "{}", arc),
             Fragment::Polygon(polygon) => write!(f, "{}", polygon),
             Fragment::Rect(rect) => write!(f, "{}", rect),
+            Fragment::Cylinder(cylinder) => write!(f, "{}", cylinder),
+            Fragment::Gate(gate) => write!(f, "{}", gate),
+            Fragment::Table(table) => write!(f, "{}", table),
+            Fragment::IsometricBox(isometric_box) => {
+                write!(f, "{}", isometric_box)
+            }
+            Fragment::Chain(chain) => write!(f, "{}", chain),
+            Fragment::Lifeline(lifeline) => write!(f, "{}", lifeline),
+            Fragment::Message(message) => write!(f, "{}", message),
+            Fragment::Snippet(snippet) => write!(f, "{}", snippet),
             Fragment::Text(text) => write!(f, "{}", text),
             Fragment::CellText(ctext) => write!(f, "{}", ctext),
         }
@@ -511,6 +703,25 @@ pub fn broken_line(a: Point, b: Point) -> Fragment {
     Fragment::Line(Line::new(a, b, true))
 }
 
+pub fn heavy_line(a: Point, b: Point) -> Fragment {
+    Fragment::Line(Line::new_heavy(a, b, false))
+}
+
+pub fn dotted_line(a: Point, b: Point) -> Fragment {
+    Fragment::Line(Line::new_dashed(a, b, DashStyle::Dotted))
+}
+
+pub fn dashed_line(a: Point, b: Point, dash: DashStyle) -> Fragment {
+    Fragment::Line(Line::new_dashed(a, b, dash))
+}
+
+pub fn heavy_dashed_line(a: Point, b: Point, dash: DashStyle) -> Fragment {
+    Fragment::Line(Line {
+        is_heavy: true,
+        ..Line::new_dashed(a, b, dash)
+    })
+}
+
 pub fn circle(c: Point, r: f32, is_filled: bool) -> Fragment {
     Fragment::Circle(Circle::new(c, r, is_filled))
 }
@@ -545,6 +756,10 @@ pub fn rect(
     Fragment::Rect(Rect::new(start, end, is_filled, is_broken))
 }
 
+pub fn shaded_rect(start: Point, end: Point, shade: Shade) -> Fragment {
+    Fragment::Rect(Rect::shaded_new(start, end, shade))
+}
+
 pub fn rounded_rect(
     start: Point,
     end: Point,
@@ -593,6 +808,60 @@ impl From<CellText> for Fragment {
     }
 }
 
+impl From<Polygon> for Fragment {
+    fn from(polygon: Polygon) -> Self {
+        Fragment::Polygon(polygon)
+    }
+}
+
+impl From<Cylinder> for Fragment {
+    fn from(cylinder: Cylinder) -> Self {
+        Fragment::Cylinder(cylinder)
+    }
+}
+
+impl From<Gate> for Fragment {
+    fn from(gate: Gate) -> Self {
+        Fragment::Gate(gate)
+    }
+}
+
+impl From<Table> for Fragment {
+    fn from(table: Table) -> Self {
+        Fragment::Table(table)
+    }
+}
+
+impl From<IsometricBox> for Fragment {
+    fn from(isometric_box: IsometricBox) -> Self {
+        Fragment::IsometricBox(isometric_box)
+    }
+}
+
+impl From<Chain> for Fragment {
+    fn from(chain: Chain) -> Self {
+        Fragment::Chain(chain)
+    }
+}
+
+impl From<Lifeline> for Fragment {
+    fn from(lifeline: Lifeline) -> Self {
+        Fragment::Lifeline(lifeline)
+    }
+}
+
+impl From<Message> for Fragment {
+    fn from(message: Message) -> Self {
+        Fragment::Message(message)
+    }
+}
+
+impl From<Snippet> for Fragment {
+    fn from(snippet: Snippet) -> Self {
+        Fragment::Snippet(snippet)
+    }
+}
+
 impl From<Circle> for Fragment {
     fn from(circle: Circle) -> Self {
         Fragment::Circle(circle)
@@ -619,6 +888,29 @@ impl Ord for Fragment {
                 polygon.cmp(other_polygon)
             } //Note: the tags are not compared here
             (Fragment::Rect(rect), Fragment::Rect(other)) => rect.cmp(other),
+            (Fragment::Cylinder(cylinder), Fragment::Cylinder(other)) => {
+                cylinder.cmp(other)
+            }
+            (Fragment::Gate(gate), Fragment::Gate(other)) => gate.cmp(other),
+            (Fragment::Table(table), Fragment::Table(other)) => {
+                table.cmp(other)
+            }
+            (
+                Fragment::IsometricBox(isometric_box),
+                Fragment::IsometricBox(other),
+            ) => isometric_box.cmp(other),
+            (Fragment::Chain(chain), Fragment::Chain(other)) => {
+                chain.cmp(other)
+            }
+            (Fragment::Lifeline(lifeline), Fragment::Lifeline(other)) => {
+                lifeline.cmp(other)
+            }
+            (Fragment::Message(message), Fragment::Message(other)) => {
+                message.cmp(other)
+            }
+            (Fragment::Snippet(snippet), Fragment::Snippet(other)) => {
+                snippet.cmp(other)
+            }
             (Fragment::Text(text), Fragment::Text(other)) => text.cmp(other),
             (Fragment::CellText(ctext), Fragment::CellText(other)) => {
                 ctext.cmp(other)
@@ -670,6 +962,79 @@ mod tests {
         assert!(!rect2.can_fit(&text2));
     }
 
+    #[test]
+    fn test_merge_filled_rects() {
+        let block1 =
+            rect(Point::new(0.0, 0.0), Point::new(1.0, 2.0), true, false);
+        let block2 =
+            rect(Point::new(1.0, 0.0), Point::new(2.0, 2.0), true, false);
+        let lower_half =
+            rect(Point::new(2.0, 1.0), Point::new(3.0, 2.0), true, false);
+        let shade = shaded_rect(
+            Point::new(1.0, 2.0),
+            Point::new(2.0, 4.0),
+            Shade::Light,
+        );
+        assert_eq!(
+            block1.merge(&block2),
+            Some(rect(
+                Point::new(0.0, 0.0),
+                Point::new(2.0, 2.0),
+                true,
+                false
+            ))
+        );
+        // the sides don't match
+        assert!(block2.merge(&lower_half).is_none());
+        // different shades
+        assert!(block2.merge(&shade).is_none());
+    }
+
+    #[test]
+    fn test_merge_marker_lines() {
+        let left_arrow = marker_line(
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+            false,
+            None,
+            Some(Marker::Arrow),
+        );
+        let dash: Fragment =
+            Line::new(Point::new(1.0, 1.0), Point::new(2.0, 1.0), false).into();
+        let right_arrow = marker_line(
+            Point::new(2.0, 1.0),
+            Point::new(3.0, 1.0),
+            false,
+            None,
+            Some(Marker::Arrow),
+        );
+        let left_half = left_arrow.merge(&dash).expect("must merge");
+        assert_eq!(
+            left_half,
+            marker_line(
+                Point::new(0.0, 1.0),
+                Point::new(2.0, 1.0),
+                false,
+                Some(Marker::Arrow),
+                None
+            )
+        );
+        assert_eq!(
+            left_half.merge(&right_arrow),
+            Some(marker_line(
+                Point::new(0.0, 1.0),
+                Point::new(3.0, 1.0),
+                false,
+                Some(Marker::Arrow),
+                Some(Marker::Arrow)
+            ))
+        );
+        // the arrow head would end up in the middle of the line
+        assert!(right_arrow
+            .merge(&left_arrow.absolute_position(Cell::new(3, 0)))
+            .is_none());
+    }
+
     #[test]
     fn test_recursive_merge() {
         let k = CellGrid::k();
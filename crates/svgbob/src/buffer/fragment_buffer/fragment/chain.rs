@@ -0,0 +1,865 @@
+use crate::{
+    buffer::{CellGrid, FragmentSpan},
+    fragment::{Bounds, DashStyle, Marker},
+    util, Cell, Fragment, Point, Settings,
+};
+use sauron::{
+    html::attributes::classes_flag,
+    svg::{attributes::*, *},
+    Node,
+};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+/// A piece of a chain, going from the end of the previous link
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChainLink {
+    /// a straight line to this point
+    Line(Point),
+    /// an arc to this point
+    Arc {
+        end: Point,
+        radius: f32,
+        major_flag: bool,
+        sweep_flag: bool,
+    },
+    /// a cubic bezier curve to this point
+    Curve {
+        control1: Point,
+        control2: Point,
+        end: Point,
+    },
+}
+
+/// Lines and arcs connected end to end, drawn as a single path
+/// so the dash pattern continues around the corners and the whole
+/// connector can be styled as one object.
+///
+/// ```ignore
+///    .-----------.
+///    |           |
+///    '----->     '---->
+/// ```
+#[derive(Debug, Clone)]
+pub struct Chain {
+    pub start: Point,
+    pub links: Vec<ChainLink>,
+    /// the chain ends where it started
+    pub is_closed: bool,
+    pub dash: DashStyle,
+    pub is_heavy: bool,
+    pub start_marker: Option<Marker>,
+    pub end_marker: Option<Marker>,
+}
+
+impl ChainLink {
+    fn end(&self) -> Point {
+        match self {
+            ChainLink::Line(end) => *end,
+            ChainLink::Arc { end, .. } => *end,
+            ChainLink::Curve { end, .. } => *end,
+        }
+    }
+
+    /// this link with its points moved by `f`
+    fn map_points(&self, f: impl Fn(Point) -> Point) -> Self {
+        match *self {
+            ChainLink::Line(end) => ChainLink::Line(f(end)),
+            ChainLink::Arc {
+                end,
+                radius,
+                major_flag,
+                sweep_flag,
+            } => ChainLink::Arc {
+                end: f(end),
+                radius,
+                major_flag,
+                sweep_flag,
+            },
+            ChainLink::Curve {
+                control1,
+                control2,
+                end,
+            } => ChainLink::Curve {
+                control1: f(control1),
+                control2: f(control2),
+                end: f(end),
+            },
+        }
+    }
+
+    fn scale(&self, scale: f32) -> Self {
+        match self.map_points(|p| p.scale(scale)) {
+            ChainLink::Arc {
+                end,
+                radius,
+                major_flag,
+                sweep_flag,
+            } => ChainLink::Arc {
+                end,
+                radius: radius * scale,
+                major_flag,
+                sweep_flag,
+            },
+            link => link,
+        }
+    }
+
+    /// used in ordering the links of different kinds
+    fn kind(&self) -> u8 {
+        match self {
+            ChainLink::Line(_) => 0,
+            ChainLink::Arc { .. } => 1,
+            ChainLink::Curve { .. } => 2,
+        }
+    }
+
+    fn path_data(&self) -> String {
+        match self {
+            ChainLink::Line(end) => format!("L {},{}", end.x, end.y),
+            ChainLink::Arc {
+                end,
+                radius,
+                major_flag,
+                sweep_flag,
+            } => format!(
+                "A {},{} 0,{},{} {},{}",
+                radius,
+                radius,
+                *major_flag as u8,
+                *sweep_flag as u8,
+                end.x,
+                end.y
+            ),
+            ChainLink::Curve {
+                control1,
+                control2,
+                end,
+            } => format!(
+                "C {},{} {},{} {},{}",
+                control1.x, control1.y, control2.x, control2.y, end.x, end.y
+            ),
+        }
+    }
+}
+
+/// A line, marker line or arc oriented from `start` to `end`,
+/// waiting to be linked into a chain
+#[derive(Debug, Clone)]
+struct Piece {
+    /// the index of the fragment span this piece is made from
+    index: usize,
+    start: Point,
+    end: Point,
+    /// the link going from start to end
+    link: ChainLink,
+    /// the link going from end to start
+    back_link: ChainLink,
+    /// arcs has no dash style and weight, they take on the ones of the lines
+    dash: Option<DashStyle>,
+    is_heavy: Option<bool>,
+    start_marker: Option<Marker>,
+    end_marker: Option<Marker>,
+}
+
+impl Piece {
+    fn from_fragment(index: usize, fragment: &Fragment) -> Option<Self> {
+        match fragment {
+            Fragment::Line(line) => Some(Piece {
+                index,
+                start: line.start,
+                end: line.end,
+                link: ChainLink::Line(line.end),
+                back_link: ChainLink::Line(line.start),
+                dash: Some(line.dash),
+                is_heavy: Some(line.is_heavy),
+                start_marker: None,
+                end_marker: None,
+            }),
+            Fragment::MarkerLine(mline) if mline.mid_markers.is_empty() => {
+                Some(Piece {
+                    start_marker: mline.start_marker.clone(),
+                    end_marker: mline.end_marker.clone(),
+                    ..Piece::from_fragment(
+                        index,
+                        &Fragment::Line(mline.line.clone()),
+                    )?
+                })
+            }
+            Fragment::Arc(arc) => Some(Piece {
+                index,
+                start: arc.start,
+                end: arc.end,
+                link: ChainLink::Arc {
+                    end: arc.end,
+                    radius: arc.radius,
+                    major_flag: arc.major_flag,
+                    sweep_flag: arc.sweep_flag,
+                },
+                back_link: ChainLink::Arc {
+                    end: arc.start,
+                    radius: arc.radius,
+                    major_flag: arc.major_flag,
+                    sweep_flag: !arc.sweep_flag,
+                },
+                dash: None,
+                is_heavy: None,
+                start_marker: None,
+                end_marker: None,
+            }),
+            _ => None,
+        }
+    }
+
+    fn reversed(&self) -> Self {
+        Piece {
+            start: self.end,
+            end: self.start,
+            link: self.back_link,
+            back_link: self.link,
+            start_marker: self.end_marker.clone(),
+            end_marker: self.start_marker.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// this piece oriented such that it starts at `p`
+    fn starting_at(&self, p: Point) -> Option<Self> {
+        if self.start == p {
+            Some(self.clone())
+        } else if self.end == p {
+            Some(self.reversed())
+        } else {
+            None
+        }
+    }
+}
+
+/// pieces can be linked if they have the same dash style and weight
+fn is_compatible(pieces: &[Piece], next: &Piece) -> bool {
+    let dash = pieces.iter().find_map(|piece| piece.dash);
+    let is_heavy = pieces.iter().find_map(|piece| piece.is_heavy);
+    (dash.is_none() || next.dash.is_none() || dash == next.dash)
+        && (is_heavy.is_none()
+            || next.is_heavy.is_none()
+            || is_heavy == next.is_heavy)
+}
+
+impl Chain {
+    fn from_pieces(pieces: &[Piece]) -> Self {
+        let first = &pieces[0];
+        let last = &pieces[pieces.len() - 1];
+        Chain {
+            start: first.start,
+            links: pieces.iter().map(|piece| piece.link).collect(),
+            is_closed: pieces.len() > 2 && last.end == first.start,
+            dash: pieces
+                .iter()
+                .find_map(|piece| piece.dash)
+                .unwrap_or_default(),
+            is_heavy: pieces
+                .iter()
+                .find_map(|piece| piece.is_heavy)
+                .unwrap_or(false),
+            start_marker: first.start_marker.clone(),
+            end_marker: last.end_marker.clone(),
+        }
+    }
+
+    /// Link the lines, marker lines and arcs in this group which are connected
+    /// end to end into chains.
+    ///
+    /// A chain goes on through a point where exactly 2 of the pieces meet,
+    /// it stops at a junction, at a marker or when the dash style or the weight
+    /// of the next piece is different.
+    /// Pieces that are not linked to any other piece are left as they are.
+    ///
+    /// The turns of the chains at the `+` characters are rounded with the
+    /// `plus_corner_radius` and the staircases are smoothed into curves
+    /// when `smooth_staircases` is set in the settings.
+    pub(crate) fn chain_fragment_spans(
+        fragment_spans: Vec<FragmentSpan>,
+        settings: &Settings,
+    ) -> Vec<FragmentSpan> {
+        let plus_corners: BTreeSet<Point> = fragment_spans
+            .iter()
+            .flat_map(|fs| fs.span.iter())
+            .filter(|(_cell, ch)| *ch == '+')
+            .map(|(cell, _ch)| cell.absolute_position(CellGrid::m()))
+            .collect();
+        let pieces: Vec<Piece> = fragment_spans
+            .iter()
+            .enumerate()
+            .filter_map(|(index, fs)| Piece::from_fragment(index, &fs.fragment))
+            .collect();
+        // the pieces which has an end at each point
+        let mut at_point: BTreeMap<Point, Vec<usize>> = BTreeMap::new();
+        for (i, piece) in pieces.iter().enumerate() {
+            at_point.entry(piece.start).or_default().push(i);
+            at_point.entry(piece.end).or_default().push(i);
+        }
+        let is_pass_through =
+            |p: Point| at_point.get(&p).is_some_and(|ends| ends.len() == 2);
+
+        let mut used = vec![false; pieces.len()];
+        let mut chains: Vec<Vec<Piece>> = vec![];
+        // the open chains starts at the pieces which has an end which is not
+        // passed through, the rest of the pieces are in closed loops
+        let open_ends = pieces.iter().enumerate().filter_map(|(i, piece)| {
+            if !is_pass_through(piece.start) {
+                Some((i, piece.clone()))
+            } else if !is_pass_through(piece.end) {
+                Some((i, piece.reversed()))
+            } else {
+                None
+            }
+        });
+        let loops = pieces
+            .iter()
+            .enumerate()
+            .map(|(i, piece)| (i, piece.clone()));
+        for (i, first) in open_ends.collect::<Vec<_>>().into_iter().chain(loops)
+        {
+            if used[i] {
+                continue;
+            }
+            used[i] = true;
+            let mut chain = vec![first];
+            loop {
+                let last = &chain[chain.len() - 1];
+                let p = last.end;
+                if !is_pass_through(p) || last.end_marker.is_some() {
+                    break;
+                }
+                let next = at_point[&p].iter().find_map(|j| {
+                    if used[*j] {
+                        None
+                    } else {
+                        pieces[*j].starting_at(p).map(|piece| (*j, piece))
+                    }
+                });
+                match next {
+                    Some((j, next))
+                        if next.start_marker.is_none()
+                            && is_compatible(&chain, &next) =>
+                    {
+                        used[j] = true;
+                        chain.push(next);
+                    }
+                    _ => break,
+                }
+            }
+            chains.push(chain);
+        }
+
+        let linked: BTreeSet<usize> = chains
+            .iter()
+            .filter(|chain| chain.len() > 1)
+            .flat_map(|chain| chain.iter().map(|piece| piece.index))
+            .collect();
+        let mut result: Vec<FragmentSpan> = fragment_spans
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !linked.contains(index))
+            .map(|(_, fs)| fs.clone())
+            .collect();
+        result.extend(chains.iter().filter(|chain| chain.len() > 1).map(
+            |chain| {
+                let span = chain.iter().skip(1).fold(
+                    fragment_spans[chain[0].index].span.clone(),
+                    |span, piece| {
+                        span.merge_no_check(&fragment_spans[piece.index].span)
+                    },
+                );
+                let chain = Chain::from_pieces(chain);
+                let chain = match settings.plus_corner_radius {
+                    Some(radius) => chain.round_corners(&plus_corners, radius),
+                    None => chain,
+                };
+                let chain = if settings.smooth_staircases {
+                    chain.smoothed()
+                } else {
+                    chain
+                };
+                FragmentSpan::new(span, Fragment::Chain(chain))
+            },
+        ));
+        result
+    }
+
+    /// the rounding of the turn at the start of the link at `index`,
+    /// which is only possible at the `corners` in between 2 perpendicular lines.
+    ///
+    /// returns the points where the arc starts and ends, its radius and
+    /// its sweep flag
+    fn corner_rounding(
+        &self,
+        index: usize,
+        corners: &BTreeSet<Point>,
+        radius: f32,
+    ) -> Option<(Point, Point, f32, bool)> {
+        let points = self.points();
+        let n = self.links.len();
+        let prev_index = (index + n - 1) % n;
+        let (ChainLink::Line(_), ChainLink::Line(next)) =
+            (self.links[prev_index], self.links[index])
+        else {
+            return None;
+        };
+        let (prev, corner) = (points[prev_index], points[index]);
+        if !corners.contains(&corner) {
+            return None;
+        }
+        let (len1, len2) = (prev.distance(&corner), corner.distance(&next));
+        let d1 = (corner - prev).normalize();
+        let d2 = (next - corner).normalize();
+        if (d1.x * d2.x + d1.y * d2.y).abs() > f32::EPSILON {
+            return None;
+        }
+        let radius = radius.min(len1 / 2.0).min(len2 / 2.0);
+        Some((
+            corner - d1.scale(radius),
+            corner + d2.scale(radius),
+            radius,
+            d1.x * d2.y - d1.y * d2.x > 0.0,
+        ))
+    }
+
+    /// replace the sharp turns at the `corners` with quarter arcs of `radius`
+    fn round_corners(self, corners: &BTreeSet<Point>, radius: f32) -> Self {
+        let n = self.links.len();
+        // the rounding at the start of each link, the start of the first
+        // link can only be rounded when the chain is closed
+        let roundings: Vec<Option<(Point, Point, f32, bool)>> = (0..n)
+            .map(|index| {
+                if index > 0 || self.is_closed {
+                    self.corner_rounding(index, corners, radius)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let mut links = vec![];
+        for (index, link) in self.links.iter().enumerate() {
+            // the end of the last link is the start of the first link
+            // when the chain is closed
+            match roundings[(index + 1) % n] {
+                Some((arc_start, arc_end, radius, sweep_flag)) => {
+                    links.push(ChainLink::Line(arc_start));
+                    links.push(ChainLink::Arc {
+                        end: arc_end,
+                        radius,
+                        major_flag: false,
+                        sweep_flag,
+                    });
+                }
+                None => links.push(*link),
+            }
+        }
+        Chain {
+            start: roundings[0]
+                .map(|(_, arc_end, _, _)| arc_end)
+                .unwrap_or(self.start),
+            links,
+            ..self
+        }
+    }
+
+    /// the staircases in this chain as cubic bezier curves.
+    ///
+    /// A staircase is a run of at least 3 lines, which goes steadily in the same
+    /// overall direction while turning left and right alternately, each turn being
+    /// less than a right angle, and each step being no longer than 3 times
+    /// the shortest step.
+    ///
+    /// ```ignore
+    ///                  .------
+    ///              _.-'
+    ///          _.-'
+    ///    -----'
+    /// ```
+    /// Only the staircase is smoothed, the arc at the top and the long lines
+    /// before and after it are kept as they are.
+    /// The ends of each staircase are kept in place, so the chain stays attached
+    /// to the shapes it connects.
+    fn smoothed(self) -> Self {
+        if self.is_closed {
+            return self;
+        }
+        let points = self.points();
+        let n = self.links.len();
+        let mut links = vec![];
+        let mut index = 0;
+        while index < n {
+            let end = self.staircase_end(&points, index);
+            if end - index >= 3 {
+                let stairs = &points[index..=end];
+                let m = stairs.len() - 1;
+                // the tangents are taken across a whole step of the staircase
+                let start_tangent = (stairs[2] - stairs[0]).normalize();
+                let end_tangent = (stairs[m - 2] - stairs[m]).normalize();
+                let (control1, control2) =
+                    util::fit_cubic_bezier(stairs, start_tangent, end_tangent);
+                links.push(ChainLink::Curve {
+                    control1,
+                    control2,
+                    end: stairs[m],
+                });
+                index = end;
+            } else {
+                links.push(self.links[index]);
+                index += 1;
+            }
+        }
+        Chain { links, ..self }
+    }
+
+    /// the index of the point where the staircase starting at the link at `start`
+    /// ends, which is `start` itself when the link is not a line
+    fn staircase_end(&self, points: &[Point], start: usize) -> usize {
+        let dot = |a: Point, b: Point| a.x * b.x + a.y * b.y;
+        let cross = |a: Point, b: Point| a.x * b.y - a.y * b.x;
+        let step = |index: usize| points[index + 1] - points[index];
+        let length = |index: usize| points[index].distance(&points[index + 1]);
+        let is_line =
+            |index: usize| matches!(self.links[index], ChainLink::Line(_));
+        if !is_line(start) {
+            return start;
+        }
+        let mut end = start + 1;
+        let (mut shortest, mut longest) = (length(start), length(start));
+        while end < self.links.len() && is_line(end) {
+            let (prev, next) = (step(end - 1), step(end));
+            let is_turn = dot(prev, next) > 0.0 && cross(prev, next) != 0.0;
+            let is_alternate = end - start < 2
+                || cross(step(end - 2), prev) * cross(prev, next) < 0.0;
+            let (next_shortest, next_longest) =
+                (shortest.min(length(end)), longest.max(length(end)));
+            let is_even = next_longest <= next_shortest * 3.0;
+            let chord = points[end + 1] - points[start];
+            let is_steady =
+                (start..=end).all(|index| dot(step(index), chord) > 0.0);
+            if !is_turn || !is_alternate || !is_even || !is_steady {
+                break;
+            }
+            shortest = next_shortest;
+            longest = next_longest;
+            end += 1;
+        }
+        end
+    }
+
+    /// the start and the end points of each link of the chain
+    fn points(&self) -> Vec<Point> {
+        std::iter::once(self.start)
+            .chain(self.links.iter().map(|link| link.end()))
+            .collect()
+    }
+
+    pub(crate) fn absolute_position(&self, cell: Cell) -> Self {
+        Chain {
+            start: cell.absolute_position(self.start),
+            links: self
+                .links
+                .iter()
+                .map(|link| link.map_points(|p| cell.absolute_position(p)))
+                .collect(),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn scale(&self, scale: f32) -> Self {
+        Chain {
+            start: self.start.scale(scale),
+            links: self.links.iter().map(|link| link.scale(scale)).collect(),
+            ..self.clone()
+        }
+    }
+
+    fn path_data(&self) -> String {
+        let mut data = format!("M {},{}", self.start.x, self.start.y);
+        for link in self.links.iter() {
+            data += " ";
+            data += &link.path_data();
+        }
+        if self.is_closed {
+            data += " Z";
+        }
+        data
+    }
+}
+
+impl Bounds for Chain {
+    fn bounds(&self) -> (Point, Point) {
+        let points = self.points();
+        let min_x = points.iter().map(|p| p.x).fold(f32::MAX, f32::min);
+        let min_y = points.iter().map(|p| p.y).fold(f32::MAX, f32::min);
+        let max_x = points.iter().map(|p| p.x).fold(f32::MIN, f32::max);
+        let max_y = points.iter().map(|p| p.y).fold(f32::MIN, f32::max);
+        (Point::new(min_x, min_y), Point::new(max_x, max_y))
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CH {}", self.path_data())
+    }
+}
+
+impl<MSG> From<Chain> for Node<MSG> {
+    fn from(chain: Chain) -> Node<MSG> {
+        let start_marked = chain
+            .start_marker
+            .as_ref()
+            .map(|marker| format!("start_marked_{marker}"));
+        let end_marked = chain
+            .end_marker
+            .as_ref()
+            .map(|marker| format!("end_marked_{marker}"));
+        path(
+            [
+                d(chain.path_data()),
+                classes_flag([
+                    (chain.dash.to_string(), true),
+                    ("heavy".to_string(), chain.is_heavy),
+                    ("nofill".to_string(), true),
+                    (
+                        start_marked.clone().unwrap_or_default(),
+                        start_marked.is_some(),
+                    ),
+                    (
+                        end_marked.clone().unwrap_or_default(),
+                        end_marked.is_some(),
+                    ),
+                ]),
+            ],
+            [],
+        )
+    }
+}
+
+impl Eq for Chain {}
+
+impl Ord for Chain {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (points, other_points) = (self.points(), other.points());
+        points
+            .len()
+            .cmp(&other_points.len())
+            .then(points.cmp(&other_points))
+            .then(self.dash.cmp(&other.dash))
+            .then(self.is_heavy.cmp(&other.is_heavy))
+            .then(self.start_marker.cmp(&other.start_marker))
+            .then(self.end_marker.cmp(&other.end_marker))
+            .then_with(|| {
+                self.links
+                    .iter()
+                    .zip(other.links.iter())
+                    .map(|(link, other_link)| match (link, other_link) {
+                        (
+                            ChainLink::Arc { radius, .. },
+                            ChainLink::Arc {
+                                radius: other_radius,
+                                ..
+                            },
+                        ) => util::ord(*radius, *other_radius),
+                        (
+                            ChainLink::Curve {
+                                control1, control2, ..
+                            },
+                            ChainLink::Curve {
+                                control1: other_control1,
+                                control2: other_control2,
+                                ..
+                            },
+                        ) => control1
+                            .cmp(other_control1)
+                            .then(control2.cmp(other_control2)),
+                        _ => link.kind().cmp(&other_link.kind()),
+                    })
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+}
+
+impl PartialOrd for Chain {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Chain {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::{CellGrid, Span},
+        fragment::{arc, line, marker_line},
+    };
+
+    fn fragment_span(fragment: Fragment) -> FragmentSpan {
+        FragmentSpan::new(Span::new(Cell::new(0, 0), '-'), fragment)
+    }
+
+    #[test]
+    fn test_chain_lines_and_arcs() {
+        let a = CellGrid::a();
+        let e = CellGrid::e();
+        let k = CellGrid::k();
+        let m = CellGrid::m();
+        let w = CellGrid::w();
+        // a line going right, curving down then going down,
+        // with an arrow at the end
+        let fragments = vec![
+            fragment_span(line(k, m)),
+            fragment_span(arc(m, w, 1.0)),
+            fragment_span(marker_line(
+                w,
+                Point::new(0.5, 4.0),
+                false,
+                None,
+                Some(Marker::Arrow),
+            )),
+            fragment_span(line(a, e)),
+        ];
+        let chained =
+            Chain::chain_fragment_spans(fragments, &Settings::default());
+        // the top line is not connected to the others
+        assert_eq!(chained.len(), 2);
+        let chain = match &chained[1].fragment {
+            Fragment::Chain(chain) => chain.clone(),
+            _ => unreachable!(),
+        };
+        assert_eq!(chain.links.len(), 3);
+        assert_eq!(chain.start, k);
+        assert_eq!(chain.start_marker, None);
+        assert_eq!(chain.end_marker, Some(Marker::Arrow));
+        assert!(!chain.is_closed);
+    }
+
+    #[test]
+    fn test_junction_stops_the_chain() {
+        let k = CellGrid::k();
+        let m = CellGrid::m();
+        let o = CellGrid::o();
+        let c = CellGrid::c();
+        let fragments = vec![
+            fragment_span(line(k, m)),
+            fragment_span(line(m, o)),
+            fragment_span(line(c, m)),
+        ];
+        let chained =
+            Chain::chain_fragment_spans(fragments, &Settings::default());
+        assert_eq!(chained.len(), 3);
+        assert!(chained
+            .iter()
+            .all(|fs| matches!(fs.fragment, Fragment::Line(_))));
+    }
+
+    #[test]
+    fn test_round_plus_corner() {
+        let c = CellGrid::c();
+        let m = CellGrid::m();
+        let o = CellGrid::o();
+        let plus = |fragment| {
+            FragmentSpan::new(Span::new(Cell::new(0, 0), '+'), fragment)
+        };
+        let fragments = vec![plus(line(c, m)), plus(line(m, o))];
+        let settings = Settings {
+            plus_corner_radius: Some(0.25),
+            ..Settings::default()
+        };
+        let chained = Chain::chain_fragment_spans(fragments, &settings);
+        assert_eq!(chained.len(), 1);
+        let chain = match &chained[0].fragment {
+            Fragment::Chain(chain) => chain.clone(),
+            _ => unreachable!(),
+        };
+        assert_eq!(chain.start, c);
+        assert_eq!(
+            chain.links,
+            vec![
+                ChainLink::Line(Point::new(0.5, 0.75)),
+                ChainLink::Arc {
+                    end: Point::new(0.75, 1.0),
+                    radius: 0.25,
+                    major_flag: false,
+                    sweep_flag: false,
+                },
+                ChainLink::Line(o),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_smooth_staircase() {
+        let settings = Settings {
+            smooth_staircases: true,
+            ..Settings::default()
+        };
+        let staircase = |points: &[(f32, f32)]| {
+            points
+                .windows(2)
+                .map(|pair| {
+                    fragment_span(line(
+                        Point::new(pair[0].0, pair[0].1),
+                        Point::new(pair[1].0, pair[1].1),
+                    ))
+                })
+                .collect::<Vec<_>>()
+        };
+        let fragments =
+            staircase(&[(0.0, 0.0), (1.0, 0.0), (2.0, 2.0), (3.0, 2.0)]);
+        let chained = Chain::chain_fragment_spans(fragments, &settings);
+        let chain = match &chained[0].fragment {
+            Fragment::Chain(chain) => chain.clone(),
+            _ => unreachable!(),
+        };
+        assert_eq!(chain.start, Point::new(0.0, 0.0));
+        assert!(matches!(
+            chain.links.as_slice(),
+            [ChainLink::Curve { end, .. }] if *end == Point::new(3.0, 2.0)
+        ));
+
+        // the right angle turns are kept
+        let fragments =
+            staircase(&[(0.0, 0.0), (1.0, 0.0), (1.0, 2.0), (2.0, 2.0)]);
+        let chained = Chain::chain_fragment_spans(fragments, &settings);
+        let chain = match &chained[0].fragment {
+            Fragment::Chain(chain) => chain.clone(),
+            _ => unreachable!(),
+        };
+        assert_eq!(chain.links.len(), 3);
+
+        // only the staircase part of the chain is smoothed,
+        // the long line after it is kept
+        let fragments = staircase(&[
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 1.0),
+            (3.0, 1.0),
+            (4.0, 2.0),
+            (10.0, 2.0),
+        ]);
+        let chained = Chain::chain_fragment_spans(fragments, &settings);
+        let chain = match &chained[0].fragment {
+            Fragment::Chain(chain) => chain.clone(),
+            _ => unreachable!(),
+        };
+        assert!(matches!(
+            chain.links.as_slice(),
+            [ChainLink::Curve { end, .. }, ChainLink::Line(line_end)]
+                if *end == Point::new(4.0, 2.0)
+                    && *line_end == Point::new(10.0, 2.0)
+        ));
+    }
+}
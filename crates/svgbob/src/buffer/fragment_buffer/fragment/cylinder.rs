@@ -0,0 +1,127 @@
+use crate::{fragment::Bounds, util, Cell, Point};
+use sauron::{
+    html::attributes::*,
+    svg::{attributes::*, *},
+    Node,
+};
+use std::{cmp::Ordering, fmt};
+
+/// A cylinder, commonly used to represent a database
+///
+/// ```ignore
+///     .------.
+///     '------'
+///     |      |
+///     |      |
+///     '------'
+/// ```
+/// The top ellipse is fully visible, while only the front half
+/// of the bottom ellipse is drawn.
+#[derive(Debug, Clone)]
+pub struct Cylinder {
+    /// the top-left most point of the cylinder
+    pub start: Point,
+    /// the bottom-right most point of the cylinder
+    pub end: Point,
+    /// the vertical radius of the top and bottom ellipse
+    pub ry: f32,
+}
+
+impl Cylinder {
+    pub(crate) fn new(start: Point, end: Point, ry: f32) -> Self {
+        Cylinder { start, end, ry }
+    }
+
+    pub(crate) fn absolute_position(&self, cell: Cell) -> Self {
+        Cylinder {
+            start: cell.absolute_position(self.start),
+            end: cell.absolute_position(self.end),
+            ..*self
+        }
+    }
+
+    pub(crate) fn scale(&self, scale: f32) -> Self {
+        Cylinder {
+            start: self.start.scale(scale),
+            end: self.end.scale(scale),
+            ry: self.ry * scale,
+        }
+    }
+
+    /// the horizontal radius of the top and bottom ellipse
+    pub(crate) fn rx(&self) -> f32 {
+        (self.end.x - self.start.x) / 2.0
+    }
+
+    /// the svg path which draws the body, then the front half of the top ellipse
+    fn path_data(&self) -> String {
+        let rx = self.rx();
+        let ry = self.ry;
+        let x1 = self.start.x;
+        let x2 = self.end.x;
+        let top_cy = self.start.y + ry;
+        let bottom_cy = self.end.y - ry;
+        format!(
+            "M {x1},{top_cy} L {x1},{bottom_cy} A {rx},{ry} 0,0,0 {x2},{bottom_cy} \
+            L {x2},{top_cy} A {rx},{ry} 0,0,0 {x1},{top_cy} Z \
+            M {x1},{top_cy} A {rx},{ry} 0,0,0 {x2},{top_cy}"
+        )
+    }
+}
+
+impl Bounds for Cylinder {
+    fn bounds(&self) -> (Point, Point) {
+        (self.start, self.end)
+    }
+}
+
+impl fmt::Display for Cylinder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CY {} {} {}", self.start, self.end, self.ry)
+    }
+}
+
+impl<MSG> From<Cylinder> for Node<MSG> {
+    fn from(cy: Cylinder) -> Node<MSG> {
+        path([d(cy.path_data()), classes(["solid", "nofill"])], [])
+    }
+}
+
+impl Eq for Cylinder {}
+
+impl Ord for Cylinder {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then(self.end.cmp(&other.end))
+            .then(util::ord(self.ry, other.ry))
+    }
+}
+
+impl PartialOrd for Cylinder {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Cylinder {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cylinder_path() {
+        let cylinder =
+            Cylinder::new(Point::new(0.0, 0.0), Point::new(4.0, 10.0), 1.0);
+        assert_eq!(cylinder.rx(), 2.0);
+        assert_eq!(
+            cylinder.path_data(),
+            "M 0,1 L 0,9 A 2,1 0,0,0 4,9 L 4,1 A 2,1 0,0,0 0,1 Z M 0,1 A 2,1 0,0,0 4,1"
+        );
+    }
+}
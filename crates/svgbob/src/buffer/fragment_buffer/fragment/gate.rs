@@ -0,0 +1,261 @@
+use crate::{fragment::Bounds, Cell, Point};
+use sauron::{
+    html::attributes::*,
+    svg::{attributes::*, *},
+    Node,
+};
+use std::{cmp::Ordering, fmt};
+
+/// The kinds of logic gates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GateKind {
+    And,
+    Or,
+    Xor,
+    Not,
+}
+
+/// A logic gate, drawn as a single path
+///
+/// ```ignore
+///       .--.         .--.        .--.
+///    ---|   \     ---\   \    --\\   \        |\
+///       |    )---     )   >---   ))   >---  --| >o--
+///    ---|   /     ---/   /    --//   /        |/
+///       '--'         '--'        '--'
+///       AND          OR          XOR         NOT
+/// ```
+/// The body goes from the back of the gate to its output,
+/// the input pins are where the wires end at the back of the gate.
+///
+/// The gates are recognized only when drawn at exactly these sizes:
+/// the AND, OR and XOR gates are 5 rows tall, with `.--.` on the top and
+/// `'--'` at the bottom, and the NOT gate is 3 rows tall.
+/// The wires can connect to any of the rows in between.
+/// Gates drawn wider or taller than these are left as lines and arcs.
+#[derive(Debug, Clone)]
+pub struct Gate {
+    pub kind: GateKind,
+    /// the top of the back of the gate
+    pub start: Point,
+    /// the bottom-right most point of the gate, which is level to the output
+    pub end: Point,
+    /// the ends of the input wires, these are connected to the back of the gate
+    pub inputs: Vec<Point>,
+}
+
+impl Gate {
+    pub(crate) fn new(
+        kind: GateKind,
+        start: Point,
+        end: Point,
+        inputs: Vec<Point>,
+    ) -> Self {
+        Gate {
+            kind,
+            start,
+            end,
+            inputs,
+        }
+    }
+
+    pub(crate) fn absolute_position(&self, cell: Cell) -> Self {
+        Gate {
+            kind: self.kind,
+            start: cell.absolute_position(self.start),
+            end: cell.absolute_position(self.end),
+            inputs: self
+                .inputs
+                .iter()
+                .map(|p| cell.absolute_position(*p))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn scale(&self, scale: f32) -> Self {
+        Gate {
+            kind: self.kind,
+            start: self.start.scale(scale),
+            end: self.end.scale(scale),
+            inputs: self.inputs.iter().map(|p| p.scale(scale)).collect(),
+        }
+    }
+
+    fn height(&self) -> f32 {
+        self.end.y - self.start.y
+    }
+
+    /// the output of the gate, at the middle of its right side
+    pub fn output(&self) -> Point {
+        Point::new(self.end.x, self.start.y + self.height() / 2.0)
+    }
+
+    /// how far the back curve of the OR and XOR bulges into the body
+    fn bulge(&self) -> f32 {
+        self.height() / 4.0
+    }
+
+    /// the radius of the bubble of the NOT gate
+    fn bubble_radius(&self) -> f32 {
+        self.height() / 8.0
+    }
+
+    /// the x location of the back of the gate at `y`,
+    /// the input wires are extended up to this point
+    fn back_x(&self, y: f32) -> f32 {
+        match self.kind {
+            GateKind::And | GateKind::Not => self.start.x,
+            GateKind::Or | GateKind::Xor => {
+                // the back is a quadratic curve, which is linear along y
+                let t = (y - self.start.y) / self.height();
+                self.start.x + 2.0 * t * (1.0 - t) * self.bulge()
+            }
+        }
+    }
+
+    /// the svg path which draws the body, the extra back curve of the XOR,
+    /// the bubble of the NOT, then the wires to the inputs
+    fn path_data(&self) -> String {
+        let (x1, y1) = (self.start.x, self.start.y);
+        let (x2, y2) = (self.end.x, self.end.y);
+        let cy = self.output().y;
+        let h = self.height();
+        let mut data = match self.kind {
+            GateKind::And => {
+                let r = h / 2.0;
+                let xa = x2 - r;
+                format!(
+                    "M {x1},{y1} L {xa},{y1} A {r},{r} 0,0,1 {xa},{y2} \
+                    L {x1},{y2} Z"
+                )
+            }
+            GateKind::Or | GateKind::Xor => {
+                let b = self.bulge();
+                let xb = x1 + b;
+                let xc = x2 - h / 8.0;
+                let mut data = format!(
+                    "M {x1},{y1} L {xb},{y1} Q {xc},{y1} {x2},{cy} \
+                    Q {xc},{y2} {xb},{y2} L {x1},{y2} Q {xb},{cy} {x1},{y1} Z"
+                );
+                if self.kind == GateKind::Xor {
+                    let x0 = x1 - h / 8.0;
+                    let xd = x0 + b;
+                    data += &format!(" M {x0},{y1} Q {xd},{cy} {x0},{y2}");
+                }
+                data
+            }
+            GateKind::Not => {
+                let r = self.bubble_radius();
+                let xt = x2 - 2.0 * r;
+                format!(
+                    "M {x1},{y1} L {xt},{cy} L {x1},{y2} Z \
+                    M {xt},{cy} A {r},{r} 0,1,0 {x2},{cy} A {r},{r} 0,1,0 {xt},{cy}"
+                )
+            }
+        };
+        for input in self.inputs.iter() {
+            let xb = self.back_x(input.y);
+            data += &format!(" M {},{} L {},{}", input.x, input.y, xb, input.y);
+        }
+        data
+    }
+}
+
+impl Bounds for Gate {
+    fn bounds(&self) -> (Point, Point) {
+        let min_x =
+            self.inputs.iter().map(|p| p.x).fold(self.start.x, f32::min);
+        (Point::new(min_x, self.start.y), self.end)
+    }
+}
+
+impl fmt::Display for Gate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "G {:?} {} {}", self.kind, self.start, self.end)
+    }
+}
+
+impl fmt::Display for GateKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GateKind::And => write!(f, "and"),
+            GateKind::Or => write!(f, "or"),
+            GateKind::Xor => write!(f, "xor"),
+            GateKind::Not => write!(f, "not"),
+        }
+    }
+}
+
+impl<MSG> From<Gate> for Node<MSG> {
+    fn from(gate: Gate) -> Node<MSG> {
+        let kind_class = format!("{}_gate", gate.kind);
+        path(
+            [
+                d(gate.path_data()),
+                classes([
+                    "solid".to_string(),
+                    "nofill".to_string(),
+                    kind_class,
+                ]),
+            ],
+            [],
+        )
+    }
+}
+
+impl Eq for Gate {}
+
+impl Ord for Gate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then(self.end.cmp(&other.end))
+            .then(self.kind.cmp(&other.kind))
+            .then(self.inputs.cmp(&other.inputs))
+    }
+}
+
+impl PartialOrd for Gate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Gate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_and_gate_path() {
+        let gate = Gate::new(
+            GateKind::And,
+            Point::new(0.0, 0.0),
+            Point::new(6.0, 8.0),
+            vec![Point::new(-1.0, 2.0)],
+        );
+        assert_eq!(gate.output(), Point::new(6.0, 4.0));
+        assert_eq!(
+            gate.path_data(),
+            "M 0,0 L 2,0 A 4,4 0,0,1 2,8 L 0,8 Z M -1,2 L 0,2"
+        );
+    }
+
+    #[test]
+    fn test_or_gate_inputs_reach_the_back_curve() {
+        let gate = Gate::new(
+            GateKind::Or,
+            Point::new(0.0, 0.0),
+            Point::new(6.0, 8.0),
+            vec![],
+        );
+        assert_eq!(gate.back_x(0.0), 0.0);
+        assert_eq!(gate.back_x(4.0), 1.0);
+        assert_eq!(gate.back_x(8.0), 0.0);
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{fragment::Bounds, Cell, Point};
+use sauron::{
+    html::attributes::{class, classes},
+    svg::{attributes::*, *},
+    Node,
+};
+use std::{cmp::Ordering, fmt};
+
+/// A box drawn in isometric view, with the top and the side faces
+/// going back up and to the right of the front face.
+///
+/// ```ignore
+///     +--------+
+///    /        /|
+///   +--------+ |
+///   |        | +
+///   |        |/
+///   +--------+
+/// ```
+/// Each of the visible faces is drawn as a closed polygon with the class
+/// `top`, `side` or `front`, so they can be shaded in the legend.
+#[derive(Debug, Clone)]
+pub struct IsometricBox {
+    /// the top-left corner of the front face
+    pub start: Point,
+    /// the bottom-right corner of the front face
+    pub end: Point,
+    /// how far the back of the box is from the front face
+    pub depth: Point,
+}
+
+/// the faces of the isometric box that are visible
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Face {
+    Top,
+    Side,
+    Front,
+}
+
+impl Face {
+    fn name(&self) -> &'static str {
+        match self {
+            Face::Top => "top",
+            Face::Side => "side",
+            Face::Front => "front",
+        }
+    }
+}
+
+impl IsometricBox {
+    pub(crate) fn new(start: Point, end: Point, depth: Point) -> Self {
+        IsometricBox { start, end, depth }
+    }
+
+    pub(crate) fn absolute_position(&self, cell: Cell) -> Self {
+        IsometricBox {
+            start: cell.absolute_position(self.start),
+            end: cell.absolute_position(self.end),
+            depth: self.depth,
+        }
+    }
+
+    pub(crate) fn scale(&self, scale: f32) -> Self {
+        IsometricBox {
+            start: self.start.scale(scale),
+            end: self.end.scale(scale),
+            depth: self.depth.scale(scale),
+        }
+    }
+
+    fn top_right(&self) -> Point {
+        Point::new(self.end.x, self.start.y)
+    }
+
+    fn bottom_left(&self) -> Point {
+        Point::new(self.start.x, self.end.y)
+    }
+
+    /// the corners of the visible faces
+    pub(crate) fn faces(&self) -> [(Face, [Point; 4]); 3] {
+        let depth = self.depth;
+        [
+            (
+                Face::Top,
+                [
+                    self.start,
+                    self.start + depth,
+                    self.top_right() + depth,
+                    self.top_right(),
+                ],
+            ),
+            (
+                Face::Side,
+                [
+                    self.top_right(),
+                    self.top_right() + depth,
+                    self.end + depth,
+                    self.end,
+                ],
+            ),
+            (
+                Face::Front,
+                [self.start, self.top_right(), self.end, self.bottom_left()],
+            ),
+        ]
+    }
+
+    /// the face which contains this point
+    pub(crate) fn face_at(&self, p: Point) -> Option<Face> {
+        let depth = self.depth;
+        let is_between =
+            |v: f32, a: f32, b: f32| v >= a.min(b) && v <= a.max(b);
+        // how far back along the depth the point is, from the top or the side
+        let back_of_top = (p.y - self.start.y) / depth.y;
+        let back_of_side = (p.x - self.end.x) / depth.x;
+        if is_between(p.x, self.start.x, self.end.x)
+            && is_between(p.y, self.start.y, self.end.y)
+        {
+            Some(Face::Front)
+        } else if is_between(back_of_top, 0.0, 1.0)
+            && is_between(p.x - back_of_top * depth.x, self.start.x, self.end.x)
+        {
+            Some(Face::Top)
+        } else if is_between(back_of_side, 0.0, 1.0)
+            && is_between(
+                p.y - back_of_side * depth.y,
+                self.start.y,
+                self.end.y,
+            )
+        {
+            Some(Face::Side)
+        } else {
+            None
+        }
+    }
+
+    /// the css classes of the face which contains this point,
+    /// these are added to the text on the face so they can be styled
+    pub(crate) fn face_classes(&self, p: Point) -> Vec<String> {
+        self.face_at(p)
+            .map(|face| vec![format!("{}_face", face.name())])
+            .unwrap_or_default()
+    }
+}
+
+impl Bounds for IsometricBox {
+    fn bounds(&self) -> (Point, Point) {
+        (
+            Point::new(self.start.x, self.start.y + self.depth.y),
+            Point::new(self.end.x + self.depth.x, self.end.y),
+        )
+    }
+}
+
+impl fmt::Display for IsometricBox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "IB {} {} {}", self.start, self.end, self.depth)
+    }
+}
+
+impl<MSG> From<IsometricBox> for Node<MSG> {
+    fn from(isometric_box: IsometricBox) -> Node<MSG> {
+        let faces = isometric_box.faces().into_iter().map(|(face, corners)| {
+            polygon(
+                [
+                    points(
+                        corners
+                            .iter()
+                            .map(|p| format!("{},{}", p.x, p.y))
+                            .collect::<Vec<String>>()
+                            .join(" "),
+                    ),
+                    classes(["nofill", face.name()]),
+                ],
+                [],
+            )
+        });
+        g([class("isometric_box")], faces)
+    }
+}
+
+impl Eq for IsometricBox {}
+
+impl Ord for IsometricBox {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then(self.end.cmp(&other.end))
+            .then(self.depth.cmp(&other.depth))
+    }
+}
+
+impl PartialOrd for IsometricBox {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for IsometricBox {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_face_at() {
+        let isometric_box = IsometricBox::new(
+            Point::new(1.5, 7.0),
+            Point::new(12.5, 15.0),
+            Point::new(3.0, -6.0),
+        );
+        assert_eq!(
+            isometric_box.face_at(Point::new(6.5, 5.0)),
+            Some(Face::Top)
+        );
+        assert_eq!(
+            isometric_box.face_at(Point::new(7.0, 11.0)),
+            Some(Face::Front)
+        );
+        assert_eq!(
+            isometric_box.face_at(Point::new(14.0, 8.0)),
+            Some(Face::Side)
+        );
+        // the empty corners of the bounds
+        assert_eq!(isometric_box.face_at(Point::new(2.0, 2.0)), None);
+        assert_eq!(isometric_box.face_at(Point::new(15.0, 14.0)), None);
+    }
+}
@@ -14,11 +14,60 @@ use std::{cmp::Ordering, fmt};
 use crate::fragment::Arc;
 use sauron::{html::attributes::*, svg, svg::attributes::*, Node};
 
+/// How the stroke of a line is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum DashStyle {
+    /// a continuous stroke
+    #[default]
+    Solid,
+    /// `┄ ╌ ~` and `- - -`
+    Dashed,
+    /// `┈ ....`
+    Dotted,
+    /// alternating dashes and dots, `-.-.-`
+    DashDot,
+}
+
+impl DashStyle {
+    pub(crate) fn from_broken(is_broken: bool) -> Self {
+        if is_broken {
+            DashStyle::Dashed
+        } else {
+            DashStyle::Solid
+        }
+    }
+
+    /// the dash style of 2 lines merged together.
+    /// A solid line takes the style of the other line,
+    /// while lines of different dash styles can not be merged.
+    fn merge(self, other: Self) -> Option<Self> {
+        match (self, other) {
+            (DashStyle::Solid, dash) | (dash, DashStyle::Solid) => Some(dash),
+            (dash, other_dash) if dash == other_dash => Some(dash),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DashStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DashStyle::Solid => write!(f, "solid"),
+            // dashed lines keep the `broken` class used by existing stylesheets
+            DashStyle::Dashed => write!(f, "broken"),
+            DashStyle::Dotted => write!(f, "dotted"),
+            DashStyle::DashDot => write!(f, "dash_dot"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Line {
     pub start: Point,
     pub end: Point,
-    pub is_broken: bool,
+    pub dash: DashStyle,
+    /// heavy lines are drawn with a thicker stroke
+    pub is_heavy: bool,
 }
 
 impl Line {
@@ -28,12 +77,21 @@ impl Line {
         let mut line = Line {
             start,
             end,
-            is_broken,
+            dash: DashStyle::from_broken(is_broken),
+            is_heavy: false,
         };
         line.sort_reorder_end_points();
         line
     }
 
+    /// creates a new line with a thicker stroke
+    pub fn new_heavy(start: Point, end: Point, is_broken: bool) -> Self {
+        Line {
+            is_heavy: true,
+            ..Line::new(start, end, is_broken)
+        }
+    }
+
     /// creates a new line, but don't reorder the points
     pub(crate) fn new_noswap(
         start: Point,
@@ -43,7 +101,16 @@ impl Line {
         Line {
             start,
             end,
-            is_broken,
+            dash: DashStyle::from_broken(is_broken),
+            is_heavy: false,
+        }
+    }
+
+    /// creates a new line drawn with this dash style
+    pub fn new_dashed(start: Point, end: Point, dash: DashStyle) -> Self {
+        Line {
+            dash,
+            ..Line::new(start, end, false)
         }
     }
 
@@ -205,7 +272,9 @@ impl Line {
     /// should be equal to 0.
     /// therefore can merge
     pub(crate) fn can_merge(&self, other: &Self) -> bool {
-        self.is_touching(other)
+        self.is_heavy == other.is_heavy
+            && self.dash.merge(other.dash).is_some()
+            && self.is_touching(other)
             && util::is_collinear(&self.start, &self.end, &other.start)
             && util::is_collinear(&self.start, &self.end, &other.end)
     }
@@ -217,8 +286,12 @@ impl Line {
         if self.can_merge(other) {
             let start = std::cmp::min(self.start, other.start);
             let end = std::cmp::max(self.end, other.end);
-            // when one of them is broken line, then everything will be broken line
-            Some(Line::new(start, end, self.is_broken || other.is_broken))
+            // a solid line takes the dash style of the other line
+            Some(Line {
+                dash: self.dash.merge(other.dash)?,
+                is_heavy: self.is_heavy,
+                ..Line::new(start, end, false)
+            })
         } else {
             None
         }
@@ -245,22 +318,27 @@ impl Line {
                 Some(Marker::OpenCircle)
             };
             let new_line = if is_close_end_point {
-                Line::new_noswap(self.start, circle.center, self.is_broken)
+                Line {
+                    end: circle.center,
+                    ..self.clone()
+                }
             } else if is_close_start_point {
                 // if close to the start, swap the end points of the line
-                Line::new_noswap(self.end, circle.center, self.is_broken)
+                Line {
+                    start: self.end,
+                    end: circle.center,
+                    ..self.clone()
+                }
             } else {
                 panic!("There is no endpoint of the line is that close to the arrow");
             };
 
-            let marker_line = marker_line(
-                new_line.start,
-                new_line.end,
-                new_line.is_broken,
-                None,
-                marker,
-            );
-            Some(marker_line)
+            Some(Fragment::MarkerLine(MarkerLine {
+                line: new_line,
+                start_marker: None,
+                end_marker: marker,
+                mid_markers: vec![],
+            }))
         } else {
             None
         }
@@ -285,12 +363,12 @@ impl Line {
     }
 
     /// check if this a horizontal line
-    fn is_horizontal(&self) -> bool {
+    pub(crate) fn is_horizontal(&self) -> bool {
         self.start.y == self.end.y
     }
 
     /// check if this is a vertical line
-    fn is_vertical(&self) -> bool {
+    pub(crate) fn is_vertical(&self) -> bool {
         self.start.x == self.end.x
     }
 
@@ -325,7 +403,8 @@ impl Line {
         Line {
             start: cell.absolute_position(self.start),
             end: cell.absolute_position(self.end),
-            is_broken: self.is_broken,
+            dash: self.dash,
+            is_heavy: self.is_heavy,
         }
     }
 
@@ -333,19 +412,25 @@ impl Line {
         Line {
             start: self.start.scale(scale),
             end: self.end.scale(scale),
-            is_broken: self.is_broken,
+            dash: self.dash,
+            is_heavy: self.is_heavy,
         }
     }
 
     pub(crate) fn is_broken(&self) -> bool {
-        self.is_broken
+        self.dash != DashStyle::Solid
+    }
+
+    pub(crate) fn is_heavy(&self) -> bool {
+        self.is_heavy
     }
 
     pub fn localize(&self, cell: Cell) -> Self {
         Line {
             start: cell.localize_point(self.start),
             end: cell.localize_point(self.end),
-            is_broken: self.is_broken,
+            dash: self.dash,
+            is_heavy: self.is_heavy,
         }
     }
 
@@ -353,7 +438,8 @@ impl Line {
         Line {
             start: self.start.align(),
             end: self.end.align(),
-            is_broken: self.is_broken,
+            dash: self.dash,
+            is_heavy: self.is_heavy,
         }
     }
 
@@ -363,7 +449,11 @@ impl Line {
         let d = self.start.distance(&self.end);
         let cx = self.end.x + (self.end.x - self.start.x) / d * length;
         let cy = self.end.y + (self.end.y - self.start.y) / d * length;
-        Line::new_noswap(self.start, Point::new(cx, cy), self.is_broken)
+        Line {
+            start: self.start,
+            end: Point::new(cx, cy),
+            ..self.clone()
+        }
     }
 
     /// extend but on the opposite direction
@@ -386,7 +476,7 @@ impl Bounds for Line {
 
 impl fmt::Display for Line {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "L {} {} {}", self.start, self.end, self.is_broken)
+        write!(f, "L {} {} {:?}", self.start, self.end, self.dash)
     }
 }
 
@@ -399,8 +489,8 @@ impl<MSG> From<Line> for Node<MSG> {
                 x2(line.end.x),
                 y2(line.end.y),
                 classes_flag([
-                    ("broken", line.is_broken),
-                    ("solid", !line.is_broken),
+                    (line.dash.to_string(), true),
+                    ("heavy".to_string(), line.is_heavy),
                 ]),
             ],
             [],
@@ -422,7 +512,8 @@ impl Ord for Line {
         self.start
             .cmp(&other.start)
             .then(self.end.cmp(&other.end))
-            .then(self.is_broken.cmp(&other.is_broken))
+            .then(self.dash.cmp(&other.dash))
+            .then(self.is_heavy.cmp(&other.is_heavy))
     }
 }
 
@@ -445,6 +536,30 @@ mod tests {
         fragment_buffer::fragment::polygon::PolygonTag, CellGrid,
     };
 
+    #[test]
+    fn test_merge_dash_styles() {
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(1.0, 0.0);
+        let c = Point::new(2.0, 0.0);
+        let solid = Line::new(a, b, false);
+        let dotted = Line::new_dashed(b, c, DashStyle::Dotted);
+        let dashed = Line::new_dashed(b, c, DashStyle::Dashed);
+
+        // solid lines takes the dash style of the other line
+        assert_eq!(
+            solid.merge(&dotted),
+            Some(Line::new_dashed(a, c, DashStyle::Dotted))
+        );
+        assert_eq!(
+            Line::new_dashed(a, b, DashStyle::Dashed).merge(&dashed),
+            Some(Line::new(a, c, true))
+        );
+        // different dash styles are kept apart
+        assert!(Line::new_dashed(a, b, DashStyle::Dashed)
+            .merge(&dotted)
+            .is_none());
+    }
+
     #[test]
     fn test_extend_line() {
         let line1 = Line::new_noswap(
@@ -1,11 +1,17 @@
 use crate::{
-    fragment::{marker_line, Bounds, Cell, Fragment, Line, Polygon},
-    Point,
+    fragment::{
+        marker_line, Bounds, Cell, Fragment, Line, Polygon, PolygonTag,
+    },
+    ArrowHead, Point,
+};
+use sauron::{
+    html::attributes::class,
+    svg::{attributes::points, g, polygon},
+    Node,
 };
-use sauron::{html::attributes::class, Node};
 use std::fmt;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Marker {
     //   -->
     Arrow,
@@ -19,10 +25,24 @@ pub enum Marker {
     //    \
     //     #
     Diamond,
+    // -----<>
+    OpenDiamond,
+    // -----<*>
+    FilledDiamond,
     // -----o
     OpenCircle,
     // -----O
     BigOpenCircle,
+    // ------||
+    One,
+    // ------<
+    Many,
+    // ------o|
+    ZeroOrOne,
+    // ------|<
+    OneOrMany,
+    // ------o<
+    ZeroOrMany,
 }
 
 impl Marker {
@@ -36,6 +56,112 @@ pub struct MarkerLine {
     pub line: Line,
     pub start_marker: Option<Marker>,
     pub end_marker: Option<Marker>,
+    /// the arrowheads along the line, in between its end points
+    pub mid_markers: Vec<MidMarker>,
+}
+
+/// An arrowhead placed in the middle of a line,
+/// pointing from the base to the tip.
+///
+/// ```ignore
+///   ---->>-------
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MidMarker {
+    pub base: Point,
+    pub tip: Point,
+    pub marker: Marker,
+    /// the shape the arrowhead is drawn with
+    pub arrow_head: ArrowHead,
+}
+
+impl MidMarker {
+    pub fn new(base: Point, tip: Point, marker: Marker) -> Self {
+        MidMarker {
+            base,
+            tip,
+            marker,
+            arrow_head: ArrowHead::default(),
+        }
+    }
+
+    /// this arrowhead drawn as the `arrow_head` shape, resized around its tip
+    /// by `size`, the same way as the arrows at the end of the lines
+    pub(crate) fn with_arrow_head(
+        &self,
+        arrow_head: ArrowHead,
+        size: f32,
+    ) -> Self {
+        MidMarker {
+            base: self.tip + (self.base - self.tip).scale(size),
+            tip: self.tip,
+            marker: self.marker.clone(),
+            arrow_head,
+        }
+    }
+
+    fn absolute_position(&self, cell: Cell) -> Self {
+        MidMarker {
+            base: cell.absolute_position(self.base),
+            tip: cell.absolute_position(self.tip),
+            marker: self.marker.clone(),
+            arrow_head: self.arrow_head,
+        }
+    }
+
+    fn align(&self) -> Self {
+        MidMarker {
+            base: self.base.align(),
+            tip: self.tip.align(),
+            marker: self.marker.clone(),
+            arrow_head: self.arrow_head,
+        }
+    }
+
+    fn scale(&self, scale: f32) -> Self {
+        MidMarker {
+            base: self.base.scale(scale),
+            tip: self.tip.scale(scale),
+            marker: self.marker.clone(),
+            arrow_head: self.arrow_head,
+        }
+    }
+
+    /// the corners of the arrowhead, which is as wide as it is long
+    fn points(&self) -> [Point; 3] {
+        let half = (self.tip - self.base).scale(0.5);
+        let side = Point::new(-half.y, half.x);
+        [self.tip, self.base + side, self.base - side]
+    }
+
+    /// the tag of the arrow pointing the same way as this arrowhead
+    fn tag(&self) -> PolygonTag {
+        let d = self.tip - self.base;
+        if d.x.abs() >= d.y.abs() {
+            if d.x > 0.0 {
+                PolygonTag::ArrowRight
+            } else {
+                PolygonTag::ArrowLeft
+            }
+        } else if d.y > 0.0 {
+            PolygonTag::ArrowBottom
+        } else {
+            PolygonTag::ArrowTop
+        }
+    }
+
+    /// the arrowhead drawn as its arrow head shape.
+    /// The shaft added to the arrows at the end of the lines is left out,
+    /// since this arrowhead is already on the line.
+    fn fragments(&self) -> Vec<Fragment> {
+        Polygon::new(self.points().to_vec(), true, vec![self.tag()])
+            .arrow_head(self.arrow_head, 1.0)
+            .into_iter()
+            .filter(|frag| {
+                !matches!(frag, Fragment::Line(line) if line.has_endpoint(self.base))
+            })
+            .collect()
+    }
 }
 
 impl MarkerLine {
@@ -50,6 +176,30 @@ impl MarkerLine {
             line: Line::new_noswap(a, b, is_broken),
             start_marker,
             end_marker,
+            mid_markers: vec![],
+        }
+    }
+
+    /// place these arrowheads along the line
+    pub fn with_mid_markers(mut self, mid_markers: Vec<MidMarker>) -> Self {
+        self.mid_markers = mid_markers;
+        self
+    }
+
+    /// the arrowheads along the line drawn as the `arrow_head` shape
+    /// and resized by `size`
+    pub(crate) fn with_arrow_head(
+        &self,
+        arrow_head: ArrowHead,
+        size: f32,
+    ) -> Self {
+        MarkerLine {
+            mid_markers: self
+                .mid_markers
+                .iter()
+                .map(|mid| mid.with_arrow_head(arrow_head, size))
+                .collect(),
+            ..self.clone()
         }
     }
 
@@ -58,6 +208,11 @@ impl MarkerLine {
             line: self.line.absolute_position(cell),
             start_marker: self.start_marker.clone(),
             end_marker: self.end_marker.clone(),
+            mid_markers: self
+                .mid_markers
+                .iter()
+                .map(|mid| mid.absolute_position(cell))
+                .collect(),
         }
     }
 
@@ -66,6 +221,11 @@ impl MarkerLine {
             line: self.line.align(),
             start_marker: self.start_marker.clone(),
             end_marker: self.end_marker.clone(),
+            mid_markers: self
+                .mid_markers
+                .iter()
+                .map(|mid| mid.align())
+                .collect(),
         }
     }
 
@@ -74,9 +234,84 @@ impl MarkerLine {
             line: self.line.scale(scale),
             start_marker: self.start_marker.clone(),
             end_marker: self.end_marker.clone(),
+            mid_markers: self
+                .mid_markers
+                .iter()
+                .map(|mid| mid.scale(scale))
+                .collect(),
         }
     }
 
+    /// the marker located at this end point of the line
+    fn marker_at(&self, p: Point) -> Option<&Marker> {
+        if p == self.line.start {
+            self.start_marker.as_ref()
+        } else if p == self.line.end {
+            self.end_marker.as_ref()
+        } else {
+            None
+        }
+    }
+
+    /// returns true if a marker of this line will end up in the middle of the line
+    fn has_marker_inside(&self, line: &Line) -> bool {
+        let is_inside = |p: Point| !line.has_endpoint(p);
+        (self.start_marker.is_some() && is_inside(self.line.start))
+            || (self.end_marker.is_some() && is_inside(self.line.end))
+    }
+
+    /// merge this marker line with a plain line
+    pub(crate) fn merge_line(&self, line: &Line) -> Option<Self> {
+        self.merge(&MarkerLine {
+            line: line.clone(),
+            start_marker: None,
+            end_marker: None,
+            mid_markers: vec![],
+        })
+    }
+
+    /// merge 2 marker lines that are collinear and touching,
+    /// as long as their markers stays at the end points of the merged line
+    /// and no 2 markers are on the same end point.
+    /// The arrowheads in the middle of both lines are kept.
+    ///
+    /// ```ignore
+    ///  ←──→
+    ///  ──>>──
+    /// ```
+    pub(crate) fn merge(&self, other: &Self) -> Option<Self> {
+        // the direction of the line is kept in the markers,
+        // so the lines are reordered the same way as plain lines before merging
+        let mut self_line = self.line.clone();
+        self_line.sort_reorder_end_points();
+        let mut other_line = other.line.clone();
+        other_line.sort_reorder_end_points();
+        let line = self_line.merge(&other_line)?;
+        if self.has_marker_inside(&line) || other.has_marker_inside(&line) {
+            return None;
+        }
+        let marker_at = |p: Point| match (self.marker_at(p), other.marker_at(p))
+        {
+            (Some(_), Some(_)) => Err(()),
+            (marker, other_marker) => Ok(marker.or(other_marker).cloned()),
+        };
+        let start_marker = marker_at(line.start).ok()?;
+        let end_marker = marker_at(line.end).ok()?;
+        let mut mid_markers: Vec<MidMarker> = self
+            .mid_markers
+            .iter()
+            .chain(other.mid_markers.iter())
+            .cloned()
+            .collect();
+        mid_markers.sort();
+        Some(MarkerLine {
+            line,
+            start_marker,
+            end_marker,
+            mid_markers,
+        })
+    }
+
     /// merge this marker line to the polygon
     pub(crate) fn merge_polygon(&self, polygon: &Polygon) -> Option<Fragment> {
         let poly_center = polygon.center();
@@ -121,13 +356,12 @@ impl MarkerLine {
                 panic!("There is no endpoint close to the polygon");
             };
 
-            Some(marker_line(
-                extended_line.start,
-                extended_line.end,
-                extended_line.is_broken,
+            Some(Fragment::MarkerLine(MarkerLine {
+                line: extended_line,
                 start_marker,
                 end_marker,
-            ))
+                mid_markers: self.mid_markers.clone(),
+            }))
         } else {
             None
         }
@@ -148,8 +382,15 @@ impl fmt::Display for Marker {
             Marker::Circle => write!(f, "circle"),
             Marker::Square => write!(f, "square"),
             Marker::Diamond => write!(f, "diamond"),
+            Marker::OpenDiamond => write!(f, "open_diamond"),
+            Marker::FilledDiamond => write!(f, "filled_diamond"),
             Marker::OpenCircle => write!(f, "open_circle"),
             Marker::BigOpenCircle => write!(f, "big_open_circle"),
+            Marker::One => write!(f, "one"),
+            Marker::Many => write!(f, "many"),
+            Marker::ZeroOrOne => write!(f, "zero_or_one"),
+            Marker::OneOrMany => write!(f, "one_or_many"),
+            Marker::ZeroOrMany => write!(f, "zero_or_many"),
         }
     }
 }
@@ -174,6 +415,43 @@ impl<MSG> From<MarkerLine> for Node<MSG> {
         if let Some(end_marker) = ml.end_marker {
             classes.push(class(format!("end_marked_{}", end_marker)));
         }
-        node.with_attributes(classes)
+        let node = node.with_attributes(classes);
+        if ml.mid_markers.is_empty() {
+            node
+        } else {
+            let arrowheads = ml.mid_markers.into_iter().flat_map(|mid| {
+                if mid.arrow_head == ArrowHead::Triangle {
+                    vec![Node::from(mid)]
+                } else {
+                    mid.fragments().into_iter().map(Node::from).collect()
+                }
+            });
+            g(
+                [class("mid_marked")],
+                std::iter::once(node).chain(arrowheads),
+            )
+        }
+    }
+}
+
+impl<MSG> From<MidMarker> for Node<MSG> {
+    fn from(mid: MidMarker) -> Node<MSG> {
+        let fill = match mid.marker {
+            Marker::ClearArrow => "bg_filled",
+            _ => "filled",
+        };
+        polygon(
+            [
+                points(
+                    mid.points()
+                        .iter()
+                        .map(|p| format!("{},{}", p.x, p.y))
+                        .collect::<Vec<String>>()
+                        .join(" "),
+                ),
+                class(fill),
+            ],
+            [],
+        )
     }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    fragment::{marker_line::Marker, Bounds},
-    Cell, Direction, Point,
+    fragment::{line, marker_line::Marker, Bounds},
+    util, ArrowHead, Cell, Direction, Fragment, Point,
 };
 use nalgebra::Point2;
 use parry2d::shape::{shape::Shape, Polyline};
@@ -156,6 +156,98 @@ impl Polygon {
         }
     }
 
+    /// this arrow drawn as the `arrow_head` shape, resized around its tip
+    /// by `size`.
+    ///
+    /// The line leading to the arrow ends at the back of the arrow as it is in
+    /// the ascii art, a shaft is added when the back of the new shape is
+    /// further in, so the line still reaches it.
+    /// Polygons other than the arrows are left as they are.
+    pub(crate) fn arrow_head(
+        &self,
+        arrow_head: ArrowHead,
+        size: f32,
+    ) -> Vec<Fragment> {
+        let direction = self.tags.iter().find_map(|tag| tag.direction());
+        let is_restyled = arrow_head != ArrowHead::Triangle || size != 1.0;
+        let (Some(direction), 3, true, true) = (
+            direction,
+            self.points.len(),
+            self.get_marker() == Some(Marker::Arrow),
+            is_restyled,
+        ) else {
+            return vec![self.clone().into()];
+        };
+        let u = direction.vector();
+        let along = |p: Point| p.x * u.x + p.y * u.y;
+        let tip_index = (0..3)
+            .max_by(|a, b| {
+                util::ord(along(self.points[*a]), along(self.points[*b]))
+            })
+            .expect("must have a tip");
+        let tip = self.points[tip_index];
+        let midpoint = |a: Point, b: Point| {
+            Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)
+        };
+        let wings = |points: &[Point]| -> (Point, Point) {
+            let wings: Vec<Point> = (0..3)
+                .filter(|i| *i != tip_index)
+                .map(|i| points[i])
+                .collect();
+            (wings[0], wings[1])
+        };
+        let (wing1, wing2) = wings(&self.points);
+        let base = midpoint(wing1, wing2);
+
+        let resized: Vec<Point> = self
+            .points
+            .iter()
+            .map(|p| tip + (*p - tip).scale(size))
+            .collect();
+        let (wing1, wing2) = wings(&resized);
+        let back = midpoint(wing1, wing2);
+        let (shape, shaft_end): (Vec<Fragment>, Point) = match arrow_head {
+            ArrowHead::Triangle => (
+                vec![Polygon {
+                    points: resized,
+                    ..self.clone()
+                }
+                .into()],
+                back,
+            ),
+            ArrowHead::Hollow => (
+                vec![Polygon {
+                    points: resized,
+                    is_filled: false,
+                    ..self.clone()
+                }
+                .into()],
+                back,
+            ),
+            ArrowHead::Barbed => {
+                let notch = back + (tip - back).scale(0.25);
+                (
+                    vec![Polygon {
+                        points: vec![tip, wing1, notch, wing2],
+                        ..self.clone()
+                    }
+                    .into()],
+                    notch,
+                )
+            }
+            ArrowHead::Chevron => {
+                (vec![line(wing1, tip), line(wing2, tip)], tip)
+            }
+        };
+        if along(shaft_end) > along(base) {
+            std::iter::once(line(base, shaft_end))
+                .chain(shape)
+                .collect()
+        } else {
+            shape
+        }
+    }
+
     fn first(&self) -> Point {
         self.points[0]
     }
@@ -1,4 +1,7 @@
-use crate::{fragment::Bounds, util, Cell, Point};
+use crate::{
+    fragment::{Bounds, DashStyle, Line},
+    util, Cell, Point,
+};
 use parry2d::shape::{ConvexPolygon, Polyline, Segment, Shape};
 use sauron::{
     html::attributes::{class, classes_flag},
@@ -7,14 +10,28 @@ use sauron::{
 };
 use std::{cmp::Ordering, fmt};
 
+/// The density of the fill of the shade blocks `░ ▒ ▓`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Shade {
+    Light,
+    Medium,
+    Dark,
+}
+
 #[derive(Debug, Clone)]
 pub struct Rect {
     pub start: Point,
     pub end: Point,
     pub is_filled: bool,
     pub radius: Option<f32>,
-    //TODO:Make this as enum
-    pub is_broken: bool,
+    pub dash: DashStyle,
+    /// heavy rects are drawn with a thicker stroke
+    pub is_heavy: bool,
+    /// shaded rects are filled partially
+    pub shade: Option<Shade>,
+    /// the y location of the horizontal lines going across the rect,
+    /// dividing it into compartments such as in UML class diagrams
+    pub dividers: Vec<f32>,
 }
 
 impl Rect {
@@ -31,7 +48,10 @@ impl Rect {
             end,
             is_filled,
             radius: None,
-            is_broken,
+            dash: DashStyle::from_broken(is_broken),
+            is_heavy: false,
+            shade: None,
+            dividers: vec![],
         };
         rect.sort_reorder_end_points();
         rect
@@ -49,12 +69,23 @@ impl Rect {
             end,
             is_filled,
             radius: Some(radius),
-            is_broken,
+            dash: DashStyle::from_broken(is_broken),
+            is_heavy: false,
+            shade: None,
+            dividers: vec![],
         };
         rect.sort_reorder_end_points();
         rect
     }
 
+    /// creates a filled rect which is filled partially with the shade
+    pub(crate) fn shaded_new(start: Point, end: Point, shade: Shade) -> Self {
+        Rect {
+            shade: Some(shade),
+            ..Rect::new(start, end, true, false)
+        }
+    }
+
     /// reorder the end points swap end points such that
     /// start < end
     pub(crate) fn sort_reorder_end_points(&mut self) {
@@ -69,7 +100,12 @@ impl Rect {
         Rect {
             start: cell.absolute_position(self.start),
             end: cell.absolute_position(self.end),
-            ..*self
+            dividers: self
+                .dividers
+                .iter()
+                .map(|y| cell.absolute_position(Point::new(0.0, *y)).y)
+                .collect(),
+            ..self.clone()
         }
     }
 
@@ -78,10 +114,44 @@ impl Rect {
             start: self.start.scale(scale),
             end: self.end.scale(scale),
             radius: self.radius.map(|r| r * scale),
-            ..*self
+            dividers: self.dividers.iter().map(|y| y * scale).collect(),
+            ..self.clone()
+        }
+    }
+
+    /// the index of the compartment at `y`, counting from the top.
+    /// returns None if this rect has no compartments
+    pub(crate) fn compartment_at(&self, y: f32) -> Option<usize> {
+        if self.dividers.is_empty() {
+            None
+        } else {
+            Some(self.dividers.iter().filter(|divider| **divider < y).count())
+        }
+    }
+
+    /// the rect without the dividers
+    pub(crate) fn outline(&self) -> Self {
+        Rect {
+            dividers: vec![],
+            ..self.clone()
         }
     }
 
+    /// the lines dividing the compartments of this rect
+    pub(crate) fn divider_lines(&self) -> Vec<Line> {
+        self.dividers
+            .iter()
+            .map(|y| Line {
+                is_heavy: self.is_heavy,
+                ..Line::new_dashed(
+                    Point::new(self.start.x, *y),
+                    Point::new(self.end.x, *y),
+                    self.dash,
+                )
+            })
+            .collect()
+    }
+
     pub(crate) fn width(&self) -> f32 {
         self.end.x - self.start.x
     }
@@ -91,7 +161,40 @@ impl Rect {
     }
 
     pub(crate) fn is_broken(&self) -> bool {
-        self.is_broken
+        self.dash != DashStyle::Solid
+    }
+
+    pub(crate) fn is_heavy(&self) -> bool {
+        self.is_heavy
+    }
+
+    /// merge filled rects which shares a whole side into a bigger rect,
+    /// such that adjacent block characters are drawn as one shape
+    pub(crate) fn merge(&self, other: &Self) -> Option<Self> {
+        let is_same_kind = self.is_filled
+            && other.is_filled
+            && !self.is_rounded()
+            && !other.is_rounded()
+            && self.dash == other.dash
+            && self.is_heavy == other.is_heavy
+            && self.shade == other.shade;
+        let is_same_rows =
+            self.start.y == other.start.y && self.end.y == other.end.y;
+        let is_same_columns =
+            self.start.x == other.start.x && self.end.x == other.end.x;
+        let is_side_by_side = is_same_rows
+            && (self.end.x == other.start.x || other.end.x == self.start.x);
+        let is_stacked = is_same_columns
+            && (self.end.y == other.start.y || other.end.y == self.start.y);
+        if is_same_kind && (is_side_by_side || is_stacked) {
+            Some(Rect {
+                start: std::cmp::min(self.start, other.start),
+                end: std::cmp::max(self.end, other.end),
+                ..self.clone()
+            })
+        } else {
+            None
+        }
     }
 
     pub fn is_rounded(&self) -> bool {
@@ -145,6 +248,14 @@ impl From<Rect> for ConvexPolygon {
 
 impl<MSG> From<Rect> for Node<MSG> {
     fn from(r: Rect) -> Node<MSG> {
+        if !r.dividers.is_empty() {
+            let dividers = r.divider_lines().into_iter().map(Node::from);
+            let outline: Node<MSG> = r.outline().into();
+            return g(
+                [class("compartmented")],
+                std::iter::once(outline).chain(dividers),
+            );
+        }
         rect(
             [
                 x(r.start.x),
@@ -152,10 +263,16 @@ impl<MSG> From<Rect> for Node<MSG> {
                 width(r.width()),
                 height(r.height()),
                 classes_flag([
-                    ("broken", r.is_broken),
-                    ("solid", !r.is_broken),
-                    ("filled", r.is_filled),
-                    ("nofill", !r.is_filled),
+                    (r.dash.to_string(), true),
+                    ("filled".to_string(), r.is_filled),
+                    ("nofill".to_string(), !r.is_filled),
+                    ("heavy".to_string(), r.is_heavy),
+                    ("light_shade".to_string(), r.shade == Some(Shade::Light)),
+                    (
+                        "medium_shade".to_string(),
+                        r.shade == Some(Shade::Medium),
+                    ),
+                    ("dark_shade".to_string(), r.shade == Some(Shade::Dark)),
                 ]),
                 if let Some(radius) = r.radius {
                     rx(radius)
@@ -177,7 +294,18 @@ impl Ord for Rect {
             .then(self.end.cmp(&other.end))
             .then(self.is_filled.cmp(&other.is_filled))
             .then(util::opt_ord(self.radius, other.radius))
-            .then(self.is_broken.cmp(&other.is_broken))
+            .then(self.dash.cmp(&other.dash))
+            .then(self.is_heavy.cmp(&other.is_heavy))
+            .then(self.shade.cmp(&other.shade))
+            .then(self.dividers.len().cmp(&other.dividers.len()))
+            .then_with(|| {
+                self.dividers
+                    .iter()
+                    .zip(other.dividers.iter())
+                    .map(|(y1, y2)| util::ord(*y1, *y2))
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            })
     }
 }
 
@@ -0,0 +1,244 @@
+use crate::{
+    fragment::{Bounds, DashStyle, MarkerLine, Text},
+    Cell, Point,
+};
+use sauron::{
+    html::attributes::{class, classes},
+    svg::{attributes::*, *},
+    Node,
+};
+use std::{cmp::Ordering, fmt};
+
+/// The lifeline of a participant in a sequence diagram.
+/// It hangs from the bottom of the participant box and is drawn continuously
+/// beneath the messages crossing it.
+///
+/// ```ignore
+///   +-------+
+///   | Alice |
+///   +-------+
+///       :
+///       :     +-+
+///       +---->| |
+///       :     +-+
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lifeline {
+    /// the top of the lifeline, at the bottom of the participant box
+    pub start: Point,
+    /// the bottom of the lifeline
+    pub end: Point,
+    pub dash: DashStyle,
+    /// the top-left and bottom-right corners of the activation bars on this lifeline
+    pub activations: Vec<(Point, Point)>,
+}
+
+/// A message in a sequence diagram going from one lifeline to another,
+/// grouped together with its label.
+///
+/// ```ignore
+///       :   hello   :
+///       +---------->:
+/// ```
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub line: MarkerLine,
+    pub label: Option<Text>,
+}
+
+impl Lifeline {
+    pub(crate) fn new(
+        start: Point,
+        end: Point,
+        dash: DashStyle,
+        activations: Vec<(Point, Point)>,
+    ) -> Self {
+        Lifeline {
+            start,
+            end,
+            dash,
+            activations,
+        }
+    }
+
+    pub(crate) fn absolute_position(&self, cell: Cell) -> Self {
+        Lifeline {
+            start: cell.absolute_position(self.start),
+            end: cell.absolute_position(self.end),
+            activations: self
+                .activations
+                .iter()
+                .map(|(tl, br)| {
+                    (cell.absolute_position(*tl), cell.absolute_position(*br))
+                })
+                .collect(),
+            ..*self
+        }
+    }
+
+    pub(crate) fn scale(&self, scale: f32) -> Self {
+        Lifeline {
+            start: self.start.scale(scale),
+            end: self.end.scale(scale),
+            activations: self
+                .activations
+                .iter()
+                .map(|(tl, br)| (tl.scale(scale), br.scale(scale)))
+                .collect(),
+            ..*self
+        }
+    }
+}
+
+impl Message {
+    pub(crate) fn new(line: MarkerLine, label: Option<Text>) -> Self {
+        Message { line, label }
+    }
+
+    pub(crate) fn absolute_position(&self, cell: Cell) -> Self {
+        Message {
+            line: self.line.absolute_position(cell),
+            label: self.label.as_ref().map(|l| l.absolute_position(cell)),
+        }
+    }
+
+    pub(crate) fn scale(&self, scale: f32) -> Self {
+        Message {
+            line: self.line.scale(scale),
+            label: self.label.as_ref().map(|l| l.scale(scale)),
+        }
+    }
+}
+
+impl Bounds for Lifeline {
+    fn bounds(&self) -> (Point, Point) {
+        (self.start, self.end)
+    }
+}
+
+impl Bounds for Message {
+    fn bounds(&self) -> (Point, Point) {
+        let (start, end) = self.line.bounds();
+        match &self.label {
+            Some(label) => {
+                let (label_start, label_end) = label.bounds();
+                (
+                    Point::new(
+                        start.x.min(label_start.x),
+                        start.y.min(label_start.y),
+                    ),
+                    Point::new(end.x.max(label_end.x), end.y.max(label_end.y)),
+                )
+            }
+            None => (start, end),
+        }
+    }
+}
+
+impl fmt::Display for Lifeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "LL {} {}", self.start, self.end)
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.label {
+            Some(label) => write!(f, "MSG {} {}", self.line, label.text),
+            None => write!(f, "MSG {}", self.line),
+        }
+    }
+}
+
+impl<MSG> From<Lifeline> for Node<MSG> {
+    fn from(lifeline: Lifeline) -> Node<MSG> {
+        let lifeline_line = line(
+            [
+                x1(lifeline.start.x),
+                y1(lifeline.start.y),
+                x2(lifeline.end.x),
+                y2(lifeline.end.y),
+                classes([lifeline.dash.to_string(), "lifeline".to_string()]),
+            ],
+            [],
+        );
+        if lifeline.activations.is_empty() {
+            return lifeline_line;
+        }
+        let activations = lifeline.activations.iter().map(|(tl, br)| {
+            rect(
+                [
+                    x(tl.x),
+                    y(tl.y),
+                    width(br.x - tl.x),
+                    height(br.y - tl.y),
+                    classes(["solid", "nofill", "activation"]),
+                ],
+                [],
+            )
+        });
+        g(
+            [class("lifeline_group")],
+            std::iter::once(lifeline_line).chain(activations),
+        )
+    }
+}
+
+impl<MSG> From<Message> for Node<MSG> {
+    fn from(message: Message) -> Node<MSG> {
+        let label = message.label.map(Node::from);
+        g(
+            [class("message")],
+            std::iter::once(message.line.into()).chain(label),
+        )
+    }
+}
+
+impl Eq for Lifeline {}
+
+impl Ord for Lifeline {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then(self.end.cmp(&other.end))
+            .then(self.dash.cmp(&other.dash))
+            .then(self.activations.cmp(&other.activations))
+    }
+}
+
+impl PartialOrd for Lifeline {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Lifeline {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Message {}
+
+impl Ord for Message {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.line
+            .line
+            .cmp(&other.line.line)
+            .then(self.line.start_marker.cmp(&other.line.start_marker))
+            .then(self.line.end_marker.cmp(&other.line.end_marker))
+            .then(self.label.cmp(&other.label))
+    }
+}
+
+impl PartialOrd for Message {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Message {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
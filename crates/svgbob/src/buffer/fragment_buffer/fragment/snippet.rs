@@ -0,0 +1,130 @@
+use crate::{fragment::Bounds, util, Cell, Point};
+use sauron::{
+    html::attributes::*,
+    svg::{attributes::*, *},
+    vdom::Leaf,
+    Node,
+};
+use std::{cmp::Ordering, fmt};
+
+/// The scale at which the svg of a snippet is authored.
+/// This is the default scale of the settings, where a cell is 8 units wide
+/// and 16 units tall.
+pub const SNIPPET_SCALE: f32 = 8.0;
+
+/// A user supplied svg snippet, placed at the location of the stencil
+/// that matched it.
+///
+/// The svg is drawn relative to the top-left of the stencil template
+/// at `SNIPPET_SCALE`, and is rendered verbatim.
+#[derive(Debug, Clone)]
+pub struct Snippet {
+    /// the top-left most point of the snippet
+    pub start: Point,
+    /// the bottom-right most point of the snippet
+    pub end: Point,
+    /// the raw svg elements
+    pub svg: String,
+    /// the scale applied to the snippet
+    scale: f32,
+}
+
+impl Snippet {
+    pub(crate) fn new(start: Point, end: Point, svg: String) -> Self {
+        Snippet {
+            start,
+            end,
+            svg,
+            scale: 1.0,
+        }
+    }
+
+    pub(crate) fn absolute_position(&self, cell: Cell) -> Self {
+        Snippet {
+            start: cell.absolute_position(self.start),
+            end: cell.absolute_position(self.end),
+            ..self.clone()
+        }
+    }
+
+    pub(crate) fn scale(&self, scale: f32) -> Self {
+        Snippet {
+            start: self.start.scale(scale),
+            end: self.end.scale(scale),
+            svg: self.svg.clone(),
+            scale: self.scale * scale,
+        }
+    }
+
+    /// the transform that moves the snippet to its location
+    /// and resize it to the current scale
+    fn transform(&self) -> String {
+        let factor = self.scale / SNIPPET_SCALE;
+        format!(
+            "translate({},{}) scale({})",
+            self.start.x, self.start.y, factor
+        )
+    }
+}
+
+impl Bounds for Snippet {
+    fn bounds(&self) -> (Point, Point) {
+        (self.start, self.end)
+    }
+}
+
+impl fmt::Display for Snippet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SN {} {} {}", self.start, self.end, self.svg)
+    }
+}
+
+impl<MSG> From<Snippet> for Node<MSG> {
+    fn from(snippet: Snippet) -> Node<MSG> {
+        g(
+            [transform(snippet.transform()), class("stencil")],
+            [Node::Leaf(Leaf::Symbol(snippet.svg.into()))],
+        )
+    }
+}
+
+impl Eq for Snippet {}
+
+impl Ord for Snippet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then(self.end.cmp(&other.end))
+            .then(self.svg.cmp(&other.svg))
+            .then(util::ord(self.scale, other.scale))
+    }
+}
+
+impl PartialOrd for Snippet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Snippet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_transform() {
+        let snippet = Snippet::new(
+            Point::new(1.0, 2.0),
+            Point::new(5.0, 8.0),
+            "<circle cx=\"4\" cy=\"4\" r=\"4\"/>".to_string(),
+        )
+        .scale(16.0);
+        assert_eq!(snippet.transform(), "translate(16,32) scale(2)");
+        assert_eq!(snippet.end, Point::new(80.0, 128.0));
+    }
+}
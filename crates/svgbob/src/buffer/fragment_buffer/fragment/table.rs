@@ -0,0 +1,245 @@
+use crate::{fragment::Bounds, util, Cell, Point};
+use sauron::{
+    html::attributes::{class, classes},
+    svg::{attributes::*, *},
+    Node,
+};
+use std::{cmp::Ordering, fmt};
+
+/// A grid table, such as the ones used in reStructuredText and pandoc
+///
+/// ```ignore
+///   +------+-----+
+///   | name | age |
+///   +======+=====+
+///   | bob  | 4   |
+///   +------+-----+
+/// ```
+/// The outer border is drawn as a single rect, while the lines in between
+/// the rows and columns are drawn across the whole table.
+#[derive(Debug, Clone)]
+pub struct Table {
+    /// the top-left corner of the table
+    pub start: Point,
+    /// the bottom-right corner of the table
+    pub end: Point,
+    /// the x location of the lines in between the columns
+    pub columns: Vec<f32>,
+    /// the y location of the lines in between the rows
+    pub rows: Vec<f32>,
+    /// the index in `rows` of the line separating the header rows from the body,
+    /// which is drawn with `=` in the ascii art
+    pub header: Option<usize>,
+}
+
+impl Table {
+    pub(crate) fn new(
+        start: Point,
+        end: Point,
+        columns: Vec<f32>,
+        rows: Vec<f32>,
+        header: Option<usize>,
+    ) -> Self {
+        Table {
+            start,
+            end,
+            columns,
+            rows,
+            header,
+        }
+    }
+
+    pub(crate) fn absolute_position(&self, cell: Cell) -> Self {
+        let origin = cell.absolute_position(Point::new(0.0, 0.0));
+        Table {
+            start: cell.absolute_position(self.start),
+            end: cell.absolute_position(self.end),
+            columns: self.columns.iter().map(|x| x + origin.x).collect(),
+            rows: self.rows.iter().map(|y| y + origin.y).collect(),
+            header: self.header,
+        }
+    }
+
+    pub(crate) fn scale(&self, scale: f32) -> Self {
+        Table {
+            start: self.start.scale(scale),
+            end: self.end.scale(scale),
+            columns: self.columns.iter().map(|x| x * scale).collect(),
+            rows: self.rows.iter().map(|y| y * scale).collect(),
+            header: self.header,
+        }
+    }
+
+    /// the index of the row at `y`, counting from the top
+    pub(crate) fn row_at(&self, y: f32) -> usize {
+        self.rows.iter().filter(|row| **row < y).count()
+    }
+
+    /// the index of the column at `x`, counting from the left
+    pub(crate) fn column_at(&self, x: f32) -> usize {
+        self.columns.iter().filter(|column| **column < x).count()
+    }
+
+    /// the css classes of the table cell which contains this point,
+    /// these are added to the text inside the cell so they can be styled
+    pub(crate) fn cell_classes(&self, p: Point) -> Vec<String> {
+        let row = self.row_at(p.y);
+        let column = self.column_at(p.x);
+        let mut cell_classes = vec![];
+        if self.header.is_some_and(|header| row <= header) {
+            cell_classes.push("header".to_string());
+        }
+        cell_classes.push(format!("row_{row}"));
+        cell_classes.push(format!("column_{column}"));
+        cell_classes
+    }
+}
+
+impl Bounds for Table {
+    fn bounds(&self) -> (Point, Point) {
+        (self.start, self.end)
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "T {} {} {}x{}",
+            self.start,
+            self.end,
+            self.rows.len() + 1,
+            self.columns.len() + 1
+        )
+    }
+}
+
+impl<MSG> From<Table> for Node<MSG> {
+    fn from(table: Table) -> Node<MSG> {
+        let border = rect(
+            [
+                x(table.start.x),
+                y(table.start.y),
+                width(table.end.x - table.start.x),
+                height(table.end.y - table.start.y),
+                classes(["solid", "nofill", "table_border"]),
+            ],
+            [],
+        );
+        let rows = table.rows.iter().enumerate().map(|(index, row_y)| {
+            let row_class = if table.header == Some(index) {
+                "table_header"
+            } else {
+                "table_row"
+            };
+            line(
+                [
+                    x1(table.start.x),
+                    y1(*row_y),
+                    x2(table.end.x),
+                    y2(*row_y),
+                    classes(["solid", row_class]),
+                ],
+                [],
+            )
+        });
+        let columns = table.columns.iter().map(|column_x| {
+            line(
+                [
+                    x1(*column_x),
+                    y1(table.start.y),
+                    x2(*column_x),
+                    y2(table.end.y),
+                    classes(["solid", "table_column"]),
+                ],
+                [],
+            )
+        });
+        g(
+            [class("table")],
+            std::iter::once(border).chain(rows).chain(columns),
+        )
+    }
+}
+
+impl Eq for Table {}
+
+impl Ord for Table {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start
+            .cmp(&other.start)
+            .then(self.end.cmp(&other.end))
+            .then(self.columns.len().cmp(&other.columns.len()))
+            .then(self.rows.len().cmp(&other.rows.len()))
+            .then(self.header.cmp(&other.header))
+            .then_with(|| {
+                self.columns
+                    .iter()
+                    .zip(other.columns.iter())
+                    .chain(self.rows.iter().zip(other.rows.iter()))
+                    .map(|(a, b)| util::ord(*a, *b))
+                    .find(|ordering| *ordering != Ordering::Equal)
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+}
+
+impl PartialOrd for Table {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Table {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_with_different_lines_are_not_equal() {
+        let table = |columns: Vec<f32>, rows: Vec<f32>| {
+            Table::new(
+                Point::new(0.0, 0.0),
+                Point::new(10.0, 12.0),
+                columns,
+                rows,
+                None,
+            )
+        };
+        let mut tables = vec![
+            table(vec![5.0], vec![4.0]),
+            table(vec![3.0], vec![4.0]),
+            table(vec![5.0], vec![8.0]),
+            table(vec![5.0], vec![4.0]),
+        ];
+        tables.sort();
+        tables.dedup();
+        assert_eq!(tables.len(), 3);
+        assert_eq!(tables[0].columns, vec![3.0]);
+        assert_eq!(tables[2].rows, vec![8.0]);
+    }
+
+    #[test]
+    fn test_cell_classes() {
+        let table = Table::new(
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 12.0),
+            vec![5.0],
+            vec![4.0, 8.0],
+            Some(0),
+        );
+        assert_eq!(
+            table.cell_classes(Point::new(2.0, 2.0)),
+            vec!["header", "row_0", "column_0"]
+        );
+        assert_eq!(
+            table.cell_classes(Point::new(7.0, 10.0)),
+            vec!["row_2", "column_1"]
+        );
+    }
+}
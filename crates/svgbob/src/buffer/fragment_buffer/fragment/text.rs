@@ -225,7 +225,7 @@ mod tests {
     use crate::{
         buffer::{Cell, CellBuffer, Contacts, Span},
         fragment::CellText,
-        Settings,
+        CharacterMap, Settings,
     };
 
     #[test]
@@ -261,7 +261,8 @@ mod tests {
         let mut spans: Vec<Span> = (&cell_buffer).into();
         assert_eq!(spans.len(), 1);
         let span1 = spans.remove(0);
-        let groups: Vec<Contacts> = span1.localize().into();
+        let groups: Vec<Contacts> =
+            span1.localize().into_contacts(&CharacterMap::default());
         for (i, group) in groups.iter().enumerate() {
             println!("group{}\n{}", i, group);
         }
@@ -307,8 +308,14 @@ mod tests {
         let cell_buffer = CellBuffer::from(art);
         let mut spans: Vec<Span> = (&cell_buffer).into();
         assert_eq!(spans.len(), 2);
-        let groups2: Vec<Contacts> = spans.remove(1).localize().into();
-        let groups1: Vec<Contacts> = spans.remove(0).localize().into();
+        let groups2: Vec<Contacts> = spans
+            .remove(1)
+            .localize()
+            .into_contacts(&CharacterMap::default());
+        let groups1: Vec<Contacts> = spans
+            .remove(0)
+            .localize()
+            .into_contacts(&CharacterMap::default());
         println!("span1 groups:");
         for (i, group1) in groups1.iter().enumerate() {
             println!("\tgroup {} {}", i, group1);
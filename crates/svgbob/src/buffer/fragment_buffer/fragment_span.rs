@@ -1,4 +1,6 @@
-use crate::{buffer::Span, Cell, Fragment, Merge, Settings};
+use crate::{
+    buffer::Span, settings::BASE_STROKE_WIDTH, Cell, Fragment, Merge, Settings,
+};
 use std::{cmp::Ordering, fmt};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -41,6 +43,37 @@ impl FragmentSpan {
         }
     }
 
+    /// the arrows of this fragment drawn with the arrow head and the marker
+    /// size in the settings.
+    /// The arrows in the ascii art are part of the drawing, so unlike the
+    /// markers, they always grow with the scale.
+    /// This includes the arrowheads in the middle of the lines.
+    pub(crate) fn with_arrow_head(self, settings: &Settings) -> Vec<Self> {
+        let size = if settings.scale_markers_with_stroke {
+            settings.marker_scale * settings.stroke_width / BASE_STROKE_WIDTH
+        } else {
+            settings.marker_scale
+        };
+        match &self.fragment {
+            Fragment::MarkerLine(marker_line)
+                if !marker_line.mid_markers.is_empty() =>
+            {
+                vec![Self::new(
+                    self.span.clone(),
+                    Fragment::MarkerLine(
+                        marker_line.with_arrow_head(settings.arrow_head, size),
+                    ),
+                )]
+            }
+            Fragment::Polygon(polygon) => polygon
+                .arrow_head(settings.arrow_head, size)
+                .into_iter()
+                .map(|fragment| Self::new(self.span.clone(), fragment))
+                .collect(),
+            _ => vec![self],
+        }
+    }
+
     pub(crate) fn is_contacting(&self, other: &Self) -> bool {
         self.fragment.is_contacting(&other.fragment)
     }
@@ -1,4 +1,6 @@
-use crate::{buffer::fragment_buffer::FragmentSpan, Fragment};
+use crate::{
+    buffer::fragment_buffer::FragmentSpan, fragment::Bounds, Fragment, Point,
+};
 use sauron::{html::attributes::*, Node};
 
 /// A tree of fragments where a fragment can contain other fragments
@@ -99,14 +101,55 @@ impl FragmentTree {
     /// convert back into fragments
     fn into_nodes<MSG>(self) -> Vec<Node<MSG>> {
         let mut nodes = vec![];
-        let mut fragment_node: Node<MSG> = self.fragment.fragment.into();
+        // the css tags of a compartmented rect goes to its outline,
+        // while the enclosed fragments are tagged with the compartment they are in
+        let compartmented = self
+            .fragment
+            .fragment
+            .as_rect()
+            .filter(|rect| !rect.dividers.is_empty())
+            .cloned();
+        let table = self.fragment.fragment.as_table().cloned();
+        let isometric_box = self.fragment.fragment.as_isometric_box().cloned();
+        let mut fragment_node: Node<MSG> = match &compartmented {
+            Some(rect) => rect.outline().into(),
+            None => self.fragment.fragment.into(),
+        };
         let _css_tag_len = self.css_tag.len();
         fragment_node =
             fragment_node.merge_attributes(vec![classes(self.css_tag)]);
 
         nodes.push(fragment_node);
+        if let Some(rect) = &compartmented {
+            nodes.extend(rect.divider_lines().into_iter().map(Node::from));
+        }
         for child in self.enclosing {
-            nodes.extend(child.into_nodes())
+            let (start, end) = child.fragment.fragment.bounds();
+            let center =
+                Point::new((start.x + end.x) / 2.0, (start.y + end.y) / 2.0);
+            let mut child_classes = vec![];
+            if let Some(compartment) = compartmented
+                .as_ref()
+                .and_then(|rect| rect.compartment_at(center.y))
+            {
+                child_classes.push(format!("compartment_{compartment}"));
+            }
+            if let Some(table) = &table {
+                child_classes.extend(table.cell_classes(center));
+            }
+            if let Some(isometric_box) = &isometric_box {
+                child_classes.extend(isometric_box.face_classes(center));
+            }
+            let mut child_nodes = child.into_nodes();
+            if let Some(first) = child_nodes
+                .first_mut()
+                .filter(|_| !child_classes.is_empty())
+            {
+                *first = first
+                    .clone()
+                    .merge_attributes(vec![classes(child_classes)]);
+            }
+            nodes.extend(child_nodes)
         }
         nodes
     }
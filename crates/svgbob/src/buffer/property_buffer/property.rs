@@ -1,9 +1,6 @@
 #![allow(clippy::type_complexity)]
 use self::Signal::Strong;
-use crate::{
-    map::{ASCII_PROPERTIES, UNICODE_PROPERTIES},
-    Fragment, Point,
-};
+use crate::{map::character_map::BUILTIN_CHARACTER_MAP, Fragment, Point};
 use std::{cmp, fmt, sync::Arc};
 
 ///
@@ -113,14 +110,11 @@ impl Property {
         }
     }
 
-    /// get the matching property of this char
+    /// get the matching property of this char from the built-in characters
     /// start from the ascii_map lookup
     /// then to the unicode_map lookup when it can't find from the first map.
     pub(crate) fn from_char<'a>(ch: char) -> Option<&'a Property> {
-        match ASCII_PROPERTIES.get(&ch) {
-            Some(property) => Some(property),
-            None => UNICODE_PROPERTIES.get(&ch),
-        }
+        BUILTIN_CHARACTER_MAP.get(ch)
     }
 
     /// empty property serves as a substitute for None property for simplicity in
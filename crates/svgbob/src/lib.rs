@@ -7,19 +7,22 @@ pub mod map;
 mod merge;
 mod point;
 mod settings;
+pub mod stencil;
 pub mod util;
 
 pub use buffer::{
     fragment, fragment::Fragment, Cell, CellBuffer, Direction, FragmentBuffer,
-    FragmentSpan, Property, Signal,
+    FragmentSpan, Property, Repair, Signal,
 };
+pub use map::CharacterMap;
 pub use merge::Merge;
 pub use nalgebra;
 pub use point::Point;
 /// reexport sauron
 pub use sauron;
 pub use sauron::Node;
-pub use settings::Settings;
+pub use settings::{ArrowHead, Settings, UnknownArrowHead};
+pub use stencil::{Stencil, StencilRegistry};
 
 pub fn to_svg(ascii: &str) -> String {
     to_svg_string_pretty(ascii)
@@ -50,6 +53,12 @@ pub fn to_svg_with_settings(ascii: &str, settings: &Settings) -> String {
     buffer
 }
 
+/// the suggested fixes to the ascii art of the shapes which are drawn
+/// with small gaps or misalignments, when `tolerant_shapes` is set
+pub fn suggest_repairs(ascii: &str, settings: &Settings) -> Vec<Repair> {
+    CellBuffer::from(ascii).get_repairs(settings)
+}
+
 /// convert ascii art to svg using the size supplied
 pub fn to_svg_with_override_size(
     ascii: &str,
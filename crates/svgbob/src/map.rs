@@ -1,9 +1,33 @@
 pub use ascii_map::ASCII_PROPERTIES;
+pub use character_map::CharacterMap;
 pub use circle_map::{CIRCLES_SPAN, DIAMETER_CIRCLE};
 pub use unicode_map::{
     FRAGMENTS_UNICODE, UNICODE_FRAGMENTS, UNICODE_PROPERTIES,
 };
 
 pub(crate) mod ascii_map;
+pub(crate) mod braille_map;
+pub(crate) mod character_map;
 pub(crate) mod circle_map;
+pub(crate) mod circuit_map;
+pub(crate) mod cylinder_map;
+pub(crate) mod ending_map;
+pub(crate) mod gate_map;
+pub(crate) mod mid_marker_map;
+pub(crate) mod sequence_map;
+pub(crate) mod table_map;
 pub(crate) mod unicode_map;
+
+/// the characters of the horizontal lines, which the recognizers of the
+/// shapes drawn along the lines are looking for.
+/// These are always the built-in characters, regardless of the `CharacterMap`
+pub(crate) fn is_horizontal_line(ch: Option<&char>) -> bool {
+    matches!(ch, Some('-') | Some('─'))
+}
+
+/// the characters of the vertical lines, which the recognizers of the
+/// shapes drawn along the lines are looking for.
+/// These are always the built-in characters, regardless of the `CharacterMap`
+pub(crate) fn is_vertical_line(ch: Option<&char>) -> bool {
+    matches!(ch, Some('|') | Some('│'))
+}
@@ -7,7 +7,7 @@ use crate::{
         },
         Cell, CellGrid,
     },
-    fragment::{arc, broken_line, circle, line, polygon, rect},
+    fragment::{arc, broken_line, circle, dotted_line, line, polygon, rect},
     Fragment, Property,
     Signal::{self, Medium, Strong, Weak},
 };
@@ -242,7 +242,7 @@ pub static ASCII_PROPERTIES: Lazy<BTreeMap<char, Property>> = Lazy::new(|| {
         ////////////////////
         (
             ':',
-            vec![(Strong, vec![broken_line(c, w)])],
+            vec![(Strong, vec![dotted_line(c, w)])],
             Arc::new(
                 move |top_left,
                       top,
@@ -253,9 +253,12 @@ pub static ASCII_PROPERTIES: Lazy<BTreeMap<char, Property>> = Lazy::new(|| {
                       bottom,
                       bottom_right| {
                     vec![(
-                        top.line_strongly_overlap(r, w)
-                            || bottom.line_strongly_overlap(c, h),
-                        vec![broken_line(c, w)],
+                        (top.line_overlap(r, w)
+                            || bottom.line_overlap(c, h))
+                            // key: value
+                            && !left.is_alphabet()
+                            && !right.is_alphabet(),
+                        vec![dotted_line(c, w)],
                     )]
                 },
             ),
@@ -0,0 +1,79 @@
+use crate::{
+    buffer::{Cell, CellGrid},
+    fragment::{circle, Fragment},
+};
+
+/// the first braille pattern, which has no dots.
+/// This is treated as a whitespace, since plots use it as the background
+pub(crate) const BRAILLE_BLANK: char = '\u{2800}';
+
+/// The location of each dot of the braille pattern in the cell grid,
+/// indexed by the bit of the dot in the codepoint offset.
+///
+/// ```ignore
+///      0 1 2 3 4
+///     0┌─┬─┬─┬─┐
+///     1├─0─┼─3─┤
+///     2├─┼─┼─┼─┤
+///     3├─1─┼─4─┤
+///     4├─┼─┼─┼─┤
+///     5├─2─┼─5─┤
+///     6├─┼─┼─┼─┤
+///     7├─6─┼─7─┤
+///     8└─┴─┴─┴─┘
+/// ```
+const BRAILLE_DOTS: [(usize, usize); 8] = [
+    (1, 1),
+    (1, 3),
+    (1, 5),
+    (3, 1),
+    (3, 3),
+    (3, 5),
+    (1, 7),
+    (3, 7),
+];
+
+/// The braille patterns `⠁` to `⣿`, where each raised dot
+/// is drawn as a small filled circle.
+/// The blank pattern `⠀` is not included, since it has no dots.
+pub(crate) fn braille_fragments() -> Vec<(char, Vec<Fragment>)> {
+    let radius = Cell::unit(1) / 2.0;
+    (1..=0xFF_u32)
+        .filter_map(|bits| {
+            let ch = char::from_u32(BRAILLE_BLANK as u32 + bits)?;
+            let dots = BRAILLE_DOTS
+                .iter()
+                .enumerate()
+                .filter(|(bit, _)| bits & (1 << bit) != 0)
+                .map(|(_, (x, y))| {
+                    circle(CellGrid::point(*x, *y), radius, true)
+                })
+                .collect();
+            Some((ch, dots))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point;
+
+    #[test]
+    fn test_braille_dots() {
+        let braille = braille_fragments();
+        assert_eq!(braille.len(), 255);
+        let (ch, dots) = &braille[0];
+        assert_eq!(*ch, '⠁');
+        assert_eq!(dots.len(), 1);
+        assert_eq!(dots[0].as_circle().unwrap().center, Point::new(0.25, 0.25));
+
+        let (ch, dots) = braille.last().unwrap();
+        assert_eq!(*ch, '⣿');
+        assert_eq!(dots.len(), 8);
+
+        // dot 8 is on the bottom right
+        let (_, dots) = braille.iter().find(|(ch, _)| *ch == '⢀').unwrap();
+        assert_eq!(dots[0].as_circle().unwrap().center, Point::new(0.75, 1.75));
+    }
+}
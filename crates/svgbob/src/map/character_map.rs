@@ -0,0 +1,96 @@
+use crate::{
+    map::{ASCII_PROPERTIES, UNICODE_PROPERTIES},
+    Fragment, Property,
+};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+/// the character map with only the built-in characters
+pub(crate) static BUILTIN_CHARACTER_MAP: Lazy<CharacterMap> =
+    Lazy::new(CharacterMap::new);
+
+/// A lookup of the property of each character.
+///
+/// This starts from the built-in `ASCII_PROPERTIES` and `UNICODE_PROPERTIES`,
+/// which can then be extended or overridden with user supplied characters.
+///
+/// ```ignore
+/// let mut character_map = CharacterMap::new();
+/// // render `═` as a broken line instead of a double line
+/// character_map.register_fragments('═', vec![broken_line(k, o)]);
+/// ```
+///
+/// The map only applies to how each character is drawn on its own, and to the
+/// lines, arcs and rects built from those fragments.
+/// The recognizers of the notations which are matched character by character,
+/// such as the grid tables, the sequence diagrams, the logic gates, the circuit
+/// symbols and the line endings, only look for the built-in line characters
+/// `-` `─` `|` `│` and `+`, a registered character is not part of these.
+#[derive(Debug, Clone, Default)]
+pub struct CharacterMap {
+    /// the registered characters, these takes precedence over the built-in ones
+    properties: HashMap<char, Property>,
+}
+
+impl CharacterMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// register or override the property of a character,
+    /// with its own signal signatures and behavior
+    pub fn register(&mut self, property: Property) {
+        self.properties.insert(property.ch, property);
+    }
+
+    /// register or override a character which is always drawn as these fragments,
+    /// the same way as the characters in `UNICODE_FRAGMENTS`
+    pub fn register_fragments(&mut self, ch: char, fragments: Vec<Fragment>) {
+        self.register(Property::with_strong_fragments(ch, fragments));
+    }
+
+    /// get the matching property of this char
+    /// start from the registered characters, then from the ascii_map lookup
+    /// then to the unicode_map lookup when it can't find from the previous maps.
+    pub fn get(&self, ch: char) -> Option<&Property> {
+        self.properties
+            .get(&ch)
+            .or_else(|| ASCII_PROPERTIES.get(&ch))
+            .or_else(|| UNICODE_PROPERTIES.get(&ch))
+    }
+
+    /// returns true if the character is registered or is built-in
+    pub fn contains(&self, ch: char) -> bool {
+        self.get(ch).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::CellGrid,
+        fragment::{broken_line, line},
+    };
+
+    #[test]
+    fn test_override_builtin() {
+        let k = CellGrid::k();
+        let o = CellGrid::o();
+        let mut character_map = CharacterMap::new();
+        assert!(character_map.get('═').is_some());
+        assert!(!character_map.contains('⁓'));
+
+        character_map.register_fragments('═', vec![broken_line(k, o)]);
+        character_map.register_fragments('⁓', vec![line(k, o)]);
+
+        let double = character_map.get('═').expect("must have a property");
+        assert!(double.match_profile(&[broken_line(k, o)]));
+        assert!(character_map.contains('⁓'));
+        // the built-in is untouched
+        assert!(!BUILTIN_CHARACTER_MAP
+            .get('═')
+            .expect("must have a property")
+            .match_profile(&[broken_line(k, o)]));
+    }
+}
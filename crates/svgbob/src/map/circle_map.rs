@@ -1,7 +1,8 @@
 use crate::{
-    buffer::{CellBuffer, Contacts, Span},
+    buffer::{CellBuffer, Contacts, Repair, Span},
     fragment,
     fragment::{Arc, Circle},
+    map::character_map::BUILTIN_CHARACTER_MAP,
     Cell, Point, Settings,
 };
 use indexmap::IndexMap;
@@ -1052,8 +1053,9 @@ impl PartialOrd for DiameterArc {
     }
 }
 
+/// the circle art are drawn with the built-in characters only
 fn circle_art_to_group(art: &str) -> Vec<Contacts> {
-    circle_art_to_span(art).into()
+    circle_art_to_span(art).into_contacts(&BUILTIN_CHARACTER_MAP)
 }
 
 fn circle_art_to_span(art: &str) -> Span {
@@ -1086,6 +1088,62 @@ pub fn endorse_circle_span(search: &Span) -> Option<(&Circle, Span)> {
     })
 }
 
+/// the number of cells of the circle art for each cell that is allowed to be
+/// missing or misplaced when endorsing circles tolerantly
+const CELLS_PER_TOLERATED_MISS: usize = 8;
+
+/// endorse the span as the circle it is meant to be, when a few of the cells
+/// of the circle art are missing or have a different character.
+///
+/// ```ignore
+///     .--.
+///    (    )
+///     `--
+/// ```
+/// returns the circle at its absolute position, the cells which are not part
+/// of the circle and the repairs to the cells that are off
+pub fn endorse_tolerant_circle_span(
+    search: &Span,
+) -> Option<(Circle, Span, Vec<Repair>)> {
+    let (top_left, _) = search.bounds()?;
+    // the missing cells could be at the top or left edge of the circle
+    // which shifts the bounds of the span
+    let offsets = [(0, 0), (1, 0), (0, 1), (1, 1)];
+    CIRCLES_SPAN.iter().rev().find_map(|(circle, span)| {
+        let tolerated = span.len() / CELLS_PER_TOLERATED_MISS;
+        offsets.iter().find_map(|(dx, dy)| {
+            let origin = Cell::new(top_left.x - dx, top_left.y - dy);
+            let repairs: Vec<Repair> = span
+                .iter()
+                .map(|(cell, ch)| {
+                    (Cell::new(origin.x + cell.x, origin.y + cell.y), *ch)
+                })
+                .filter(|expected| !search.contains(expected))
+                .map(|(cell, ch)| Repair::new(cell, ch))
+                .collect();
+            if repairs.is_empty() || repairs.len() > tolerated {
+                return None;
+            }
+            let is_circle_cell = |cell: &Cell| {
+                span.iter().any(|(circle_cell, _ch)| {
+                    origin.x + circle_cell.x == cell.x
+                        && origin.y + circle_cell.y == cell.y
+                })
+            };
+            let unmatched: Vec<(Cell, char)> = search
+                .iter()
+                .filter(|(cell, _ch)| !is_circle_cell(cell))
+                .copied()
+                .collect();
+            Some((
+                circle.absolute_position(origin),
+                Span::from(unmatched),
+                repairs,
+            ))
+        })
+    })
+}
+
 pub fn endorse_quarter_arc_span(search: &Span) -> Option<(&Arc, Span)> {
     FLATTENED_QUARTER_ARC_SPAN.iter().rev().find_map(
         |(_diameter, (arc, span))| {
@@ -1,4 +1,5 @@
 use super::*;
+use crate::CharacterMap;
 
 //#[test]
 fn show_circles() {
@@ -55,7 +56,7 @@ fn test_circle1() {
     let mut spans: Vec<Span> = (&cell_buffer).into();
     assert_eq!(spans.len(), 1);
     let span1 = spans.remove(0);
-    let groups: Vec<Contacts> = span1.into();
+    let groups: Vec<Contacts> = span1.into_contacts(&CharacterMap::default());
     for (i, group) in groups.iter().enumerate() {
         println!("group{}\n{}", i, group);
     }
@@ -272,3 +273,37 @@ fn test_arc20_bottom_right() {
     let (arc, _) = endorse_quarter_arc_span(&span1).unwrap();
     assert_eq!(arc.radius, 10.0);
 }
+
+#[test]
+fn test_tolerant_circle_missing_a_cell() {
+    let art = r#"
+   .--.
+  (    )
+   `--
+            "#;
+    let cell_buffer = CellBuffer::from(art);
+    let mut spans: Vec<Span> = (&cell_buffer).into();
+    assert_eq!(spans.len(), 1);
+    let span1 = spans.remove(0);
+    assert!(endorse_circle_span(&span1).is_none());
+    let (circle, unmatched, repairs) =
+        endorse_tolerant_circle_span(&span1).unwrap();
+    assert_eq!(circle.radius, 2.5);
+    assert!(unmatched.is_empty());
+    assert_eq!(repairs, vec![Repair::new(Cell::new(6, 3), '\'')]);
+}
+
+#[test]
+fn test_tolerant_circle_missing_the_left_side() {
+    let art = r#"
+   .--.
+       )
+   `--'
+            "#;
+    let cell_buffer = CellBuffer::from(art);
+    let mut spans: Vec<Span> = (&cell_buffer).into();
+    let span1 = spans.remove(0);
+    let (_circle, _unmatched, repairs) =
+        endorse_tolerant_circle_span(&span1).unwrap();
+    assert_eq!(repairs, vec![Repair::new(Cell::new(2, 2), '(')]);
+}
@@ -0,0 +1,408 @@
+use crate::{
+    buffer::{FragmentSpan, Span},
+    fragment::{arc_with_sweep, heavy_line, line, polygon},
+    map::{is_horizontal_line, is_vertical_line},
+    Cell, Fragment, Point,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// the `|` of a component must not be part of a vertical line
+fn is_free_standing(cells: &BTreeMap<Cell, char>, cell: Cell) -> bool {
+    let is_vertical = |c: Cell| {
+        matches!(
+            cells.get(&c),
+            Some('|')
+                | Some('│')
+                | Some('+')
+                | Some('.')
+                | Some('\'')
+                | Some(':')
+        )
+    };
+    !is_vertical(cell.top()) && !is_vertical(cell.bottom())
+}
+
+/// a recognizer tries to match a component with its first cell at `cell`.
+///
+/// returns the cells of the component and its glyph, localized to that cell
+type Recognizer =
+    fn(&BTreeMap<Cell, char>, Cell) -> Option<(Vec<Cell>, Vec<Fragment>)>;
+
+/// The recognizers of the analog circuit symbols, tried in this order.
+///
+/// ```ignore
+///     -/\/\/-    resistor            |           |          |
+///     -||-       capacitor          ---        -----      -----
+///     -mmm-      inductor           ---         ---        ---
+///     ->|-       diode               |           |          -
+///     -|<-       diode            capacitor    battery    ground
+/// ```
+const RECOGNIZERS: [Recognizer; 5] = [
+    match_resistor,
+    match_capacitor,
+    match_inductor,
+    match_diode,
+    match_plates,
+];
+
+/// Find the analog circuit symbols in this span.
+///
+/// returns the glyphs of the matched components, each one is attached to the first cell
+/// of the component and localized to that cell, and the cells that are not part of
+/// any component
+pub(crate) fn endorse_circuits(
+    span: &Span,
+) -> (Vec<(Cell, FragmentSpan)>, Span) {
+    let cells: BTreeMap<Cell, char> = span.iter().copied().collect();
+    let mut matched: BTreeSet<Cell> = BTreeSet::new();
+    let mut components = vec![];
+
+    for cell in cells.keys() {
+        if matched.contains(cell) {
+            continue;
+        }
+        let component = RECOGNIZERS
+            .iter()
+            .find_map(|recognizer| recognizer(&cells, *cell))
+            .filter(|(body, _)| body.iter().all(|c| !matched.contains(c)));
+
+        if let Some((body, glyph)) = component {
+            matched.extend(body.iter().copied());
+            let body_span = Span::from(
+                body.iter()
+                    .map(|c| (*c, cells[c]))
+                    .collect::<Vec<(Cell, char)>>(),
+            );
+            components.extend(glyph.into_iter().map(|frag| {
+                (*cell, FragmentSpan::new(body_span.clone(), frag))
+            }));
+        }
+    }
+    let unmatched = span
+        .iter()
+        .filter(|(cell, _ch)| !matched.contains(cell))
+        .copied()
+        .collect::<Vec<_>>();
+    (components, Span::from(unmatched))
+}
+
+/// a wire is leading to the component at `cell`, going to the `direction`.
+/// A wire that is only 1 character long must not be attached to the side of a box,
+/// that is a crow's foot of an entity relationship diagram instead.
+///
+/// ```ignore
+///     |->|------
+/// ```
+fn has_lead(cells: &BTreeMap<Cell, char>, cell: Cell, direction: i32) -> bool {
+    let beyond = cells.get(&Cell::new(cell.x + direction, cell.y));
+    is_horizontal_line(cells.get(&cell))
+        && (is_horizontal_line(beyond) || !is_vertical_line(beyond))
+}
+
+/// the run of cells starting at `first` going to the right, while `is_body` holds
+fn body_run(
+    cells: &BTreeMap<Cell, char>,
+    first: Cell,
+    is_body: impl Fn(usize, char) -> bool,
+) -> Vec<Cell> {
+    (0..)
+        .map(|i| Cell::new(first.x + i, first.y))
+        .enumerate()
+        .take_while(|(i, cell)| {
+            cells.get(cell).map(|ch| is_body(*i, *ch)).unwrap_or(false)
+        })
+        .map(|(_, cell)| cell)
+        .collect()
+}
+
+/// an inline component is in between 2 horizontal wires
+fn is_inline(cells: &BTreeMap<Cell, char>, body: &[Cell]) -> bool {
+    match (body.first(), body.last()) {
+        (Some(first), Some(last)) => {
+            has_lead(cells, first.left(), -1)
+                && has_lead(cells, last.right(), 1)
+        }
+        _ => false,
+    }
+}
+
+/// a zigzag of alternating `/` and `\`, drawn as a zigzag with a peak on each cell
+///
+/// ```ignore
+///     -/\/\/-
+/// ```
+fn match_resistor(
+    cells: &BTreeMap<Cell, char>,
+    first: Cell,
+) -> Option<(Vec<Cell>, Vec<Fragment>)> {
+    let first_ch = *cells.get(&first)?;
+    let other_ch = match first_ch {
+        '/' => '\\',
+        '\\' => '/',
+        _ => return None,
+    };
+    let body = body_run(cells, first, |i, ch| {
+        ch == if i % 2 == 0 { first_ch } else { other_ch }
+    });
+    if body.len() < 3 || !is_inline(cells, &body) {
+        return None;
+    }
+    // the first peak goes up when the zigzag starts with `/`
+    let peak = |i: usize| {
+        let is_up = (i % 2 == 1) != (first_ch == '/');
+        Point::new(i as f32 + 0.5, if is_up { 0.5 } else { 1.5 })
+    };
+    let n = body.len();
+    let points: Vec<Point> = std::iter::once(Point::new(0.0, 1.0))
+        .chain((0..n).map(peak))
+        .chain(std::iter::once(Point::new(n as f32, 1.0)))
+        .collect();
+    let zigzag = points.windows(2).map(|w| line(w[0], w[1])).collect();
+    Some((body, zigzag))
+}
+
+/// 2 parallel plates in between the wires
+///
+/// ```ignore
+///     -||-
+/// ```
+fn match_capacitor(
+    cells: &BTreeMap<Cell, char>,
+    first: Cell,
+) -> Option<(Vec<Cell>, Vec<Fragment>)> {
+    let body = vec![first, first.right()];
+    let is_plates = body.iter().all(|cell| {
+        cells.get(cell) == Some(&'|') && is_free_standing(cells, *cell)
+    });
+    if !is_plates || !is_inline(cells, &body) {
+        return None;
+    }
+    Some((
+        body,
+        vec![
+            line(Point::new(0.0, 1.0), Point::new(0.5, 1.0)),
+            line(Point::new(0.5, 0.0), Point::new(0.5, 2.0)),
+            line(Point::new(1.5, 0.0), Point::new(1.5, 2.0)),
+            line(Point::new(1.5, 1.0), Point::new(2.0, 1.0)),
+        ],
+    ))
+}
+
+/// a coil, drawn as a half circle on each cell
+///
+/// ```ignore
+///     -mmm-
+/// ```
+fn match_inductor(
+    cells: &BTreeMap<Cell, char>,
+    first: Cell,
+) -> Option<(Vec<Cell>, Vec<Fragment>)> {
+    let body = body_run(cells, first, |_, ch| ch == 'm');
+    if body.len() < 2 || !is_inline(cells, &body) {
+        return None;
+    }
+    let coil = (0..body.len())
+        .map(|i| {
+            let x = i as f32;
+            arc_with_sweep(
+                Point::new(x, 1.0),
+                Point::new(x + 1.0, 1.0),
+                0.5,
+                true,
+            )
+        })
+        .collect();
+    Some((body, coil))
+}
+
+/// a triangle pointing to a bar, in the direction of the current
+///
+/// ```ignore
+///     ->|-   -|<-
+/// ```
+fn match_diode(
+    cells: &BTreeMap<Cell, char>,
+    first: Cell,
+) -> Option<(Vec<Cell>, Vec<Fragment>)> {
+    let body = vec![first, first.right()];
+    let glyph = match (cells.get(&body[0]), cells.get(&body[1])) {
+        (Some('>'), Some('|')) => vec![
+            polygon(
+                vec![
+                    Point::new(0.0, 0.0),
+                    Point::new(1.5, 1.0),
+                    Point::new(0.0, 2.0),
+                ],
+                true,
+                vec![],
+            ),
+            line(Point::new(1.5, 0.0), Point::new(1.5, 2.0)),
+            line(Point::new(1.5, 1.0), Point::new(2.0, 1.0)),
+        ],
+        (Some('|'), Some('<')) => vec![
+            line(Point::new(0.0, 1.0), Point::new(0.5, 1.0)),
+            line(Point::new(0.5, 0.0), Point::new(0.5, 2.0)),
+            polygon(
+                vec![
+                    Point::new(2.0, 0.0),
+                    Point::new(0.5, 1.0),
+                    Point::new(2.0, 2.0),
+                ],
+                true,
+                vec![],
+            ),
+        ],
+        _ => return None,
+    };
+    let is_bar_free = body
+        .iter()
+        .all(|cell| cells[cell] != '|' || is_free_standing(cells, *cell));
+    if !is_bar_free || !is_inline(cells, &body) {
+        return None;
+    }
+    Some((body, glyph))
+}
+
+/// The horizontal plates stacked below a vertical wire, each plate is centered on the wire.
+/// Plates of equal width connected on both ends is a capacitor,
+/// alternating long and short plates connected on both ends is a battery,
+/// and plates getting shorter going down with nothing below is a ground.
+///
+/// ```ignore
+///      |        |         |
+///     ---     -----     -----
+///     ---      ---       ---
+///      |        |         -
+/// ```
+fn match_plates(
+    cells: &BTreeMap<Cell, char>,
+    first: Cell,
+) -> Option<(Vec<Cell>, Vec<Fragment>)> {
+    if !is_vertical_line(cells.get(&first.top())) {
+        return None;
+    }
+    let is_plate = |x: i32, y: i32| cells.get(&Cell::new(x, y)) == Some(&'-');
+    // the half widths of the plates, excluding the center cell
+    let mut half_widths = vec![];
+    for y in first.y.. {
+        if !is_plate(first.x, y) {
+            break;
+        }
+        let left = (1..).take_while(|i| is_plate(first.x - i, y)).count();
+        let right = (1..).take_while(|i| is_plate(first.x + i, y)).count();
+        if left != right {
+            break;
+        }
+        half_widths.push(left as i32);
+    }
+    let n = half_widths.len();
+    if n < 2 {
+        return None;
+    }
+    let bottom = Cell::new(first.x, first.y + n as i32);
+    let has_bottom_lead = is_vertical_line(cells.get(&bottom));
+
+    let is_capacitor = n == 2 && half_widths[0] == half_widths[1];
+    let is_battery = n % 2 == 0
+        && half_widths.chunks(2).all(|pair| {
+            pair[0] > pair[1]
+                && pair[0] == half_widths[0]
+                && pair[1] == half_widths[1]
+        });
+    let is_ground = half_widths.windows(2).all(|w| w[0] > w[1]);
+
+    let has_short_plates = if has_bottom_lead && is_capacitor {
+        false
+    } else if has_bottom_lead && is_battery {
+        true
+    } else if !has_bottom_lead && is_ground {
+        false
+    } else {
+        return None;
+    };
+
+    let mut body = vec![];
+    let mut glyph = vec![line(Point::new(0.5, 0.0), Point::new(0.5, 1.0))];
+    for (j, half_width) in half_widths.iter().enumerate() {
+        let y = first.y + j as i32;
+        body.extend(
+            (first.x - half_width..=first.x + half_width)
+                .map(|x| Cell::new(x, y)),
+        );
+        let plate_y = j as f32 * 2.0 + 1.0;
+        let start = Point::new(-half_width as f32, plate_y);
+        let end = Point::new(*half_width as f32 + 1.0, plate_y);
+        // the short plate of a battery is the negative terminal,
+        // which is drawn thicker
+        if has_short_plates && j % 2 == 1 {
+            glyph.push(heavy_line(start, end));
+        } else {
+            glyph.push(line(start, end));
+        }
+    }
+    if has_bottom_lead {
+        let last_y = (n - 1) as f32 * 2.0;
+        glyph.push(line(
+            Point::new(0.5, last_y + 1.0),
+            Point::new(0.5, last_y + 2.0),
+        ));
+    }
+    Some((body, glyph))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::CellBuffer;
+
+    fn endorse(art: &str) -> (Vec<(Cell, FragmentSpan)>, Span) {
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        endorse_circuits(&spans.remove(0))
+    }
+
+    #[test]
+    fn test_resistor() {
+        let (components, unmatched) = endorse("--/\\/\\/--");
+        // 5 peaks, plus the lines to the wires
+        assert_eq!(components.len(), 6);
+        assert_eq!(unmatched.len(), 4);
+    }
+
+    #[test]
+    fn test_ground() {
+        let art = r#"
+      |
+    -----
+     ---
+      -
+            "#;
+        let (components, unmatched) = endorse(art);
+        assert_eq!(components.len(), 4);
+        assert_eq!(unmatched.len(), 1);
+    }
+
+    #[test]
+    fn test_battery_and_capacitor() {
+        let art = r#"
+      |      |
+    -----   ---
+     ---    ---
+      |      |
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let spans: Vec<Span> = (&cell_buffer).into();
+        assert_eq!(spans.len(), 2);
+        for span in spans {
+            let (components, unmatched) = endorse_circuits(&span);
+            assert_eq!(components.len(), 4);
+            assert_eq!(unmatched.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_crows_foot_is_not_diode() {
+        let (components, _unmatched) = endorse("|->|------");
+        assert!(components.is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{buffer::Span, fragment::Cylinder, Cell};
+use std::collections::BTreeMap;
+
+/// The cylinder template, the width and the height can be stretched
+/// by adding more `-` or more `|`
+///
+/// ```ignore
+///     .------.
+///     '------'
+///     |      |
+///     |      |
+///     '------'
+/// ```
+///
+/// returns the cylinder localized to the top-left of the span and the cells
+/// that are not part of the cylinder
+pub fn endorse_cylinder_span(search: &Span) -> Option<(Cylinder, Span)> {
+    let search_localized = search.clone().localize();
+    let cells: BTreeMap<Cell, char> =
+        search_localized.iter().copied().collect();
+    cells
+        .iter()
+        .filter(|(_cell, ch)| **ch == '.')
+        .find_map(|(cell, _ch)| match_cylinder(&cells, *cell))
+        .map(|(cylinder, matched)| {
+            let unmatched_cell_chars = search
+                .iter()
+                .zip(search_localized.iter())
+                .filter_map(|(cell_char, (local_cell, _))| {
+                    if matched.contains(local_cell) {
+                        None
+                    } else {
+                        Some(*cell_char)
+                    }
+                })
+                .collect::<Vec<_>>();
+            (cylinder, Span::from(unmatched_cell_chars))
+        })
+}
+
+/// try to match the cylinder template with `top_left` as the location of the
+/// top left `.` of the cylinder
+fn match_cylinder(
+    cells: &BTreeMap<Cell, char>,
+    top_left: Cell,
+) -> Option<(Cylinder, Vec<Cell>)> {
+    let is =
+        |x: i32, y: i32, ch: char| cells.get(&Cell::new(x, y)) == Some(&ch);
+    let x0 = top_left.x;
+    let y0 = top_left.y;
+
+    let x1 = (x0 + 1..)
+        .find(|x| !is(*x, y0, '-'))
+        .filter(|x1| *x1 > x0 + 1 && is(*x1, y0, '.'))?;
+
+    let mut matched = vec![];
+    let mut match_rim = |y: i32, corner: char| -> bool {
+        if is(x0, y, corner)
+            && is(x1, y, corner)
+            && (x0 + 1..x1).all(|x| is(x, y, '-'))
+        {
+            matched.extend((x0..=x1).map(|x| Cell::new(x, y)));
+            true
+        } else {
+            false
+        }
+    };
+
+    if !match_rim(y0, '.') || !match_rim(y0 + 1, '\'') {
+        return None;
+    }
+    let y_bottom = (y0 + 2..)
+        .find(|y| !(is(x0, *y, '|') && is(x1, *y, '|')))
+        .filter(|y_bottom| *y_bottom > y0 + 2)?;
+    if !match_rim(y_bottom, '\'') {
+        return None;
+    }
+    matched.extend(
+        (y0 + 2..y_bottom)
+            .flat_map(|y| [Cell::new(x0, y), Cell::new(x1, y)].into_iter()),
+    );
+
+    let start = Cell::new(x0, y0).m();
+    let end = Cell::new(x1, y_bottom).m();
+    let ry = Cell::height() / 2.0;
+    Some((Cylinder::new(start, end, ry), matched))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{buffer::CellBuffer, Point};
+
+    #[test]
+    fn test_cylinder() {
+        let art = r#"
+    .------.
+    '------'
+    |      |
+    |      |---
+    '------'
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        assert_eq!(spans.len(), 1);
+        let span1 = spans.remove(0);
+        let (cylinder, unmatched) =
+            endorse_cylinder_span(&span1).expect("must be a cylinder");
+        assert_eq!(cylinder.start, Point::new(0.5, 1.0));
+        assert_eq!(cylinder.end, Point::new(7.5, 9.0));
+        assert_eq!(unmatched.len(), 3);
+    }
+
+    #[test]
+    fn test_flat_rounded_rect_is_not_cylinder() {
+        let art = r#"
+    .------.
+    '------'
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        assert!(endorse_cylinder_span(&span1).is_none());
+    }
+}
@@ -0,0 +1,341 @@
+use crate::{
+    buffer::{CellGrid, FragmentSpan, Span},
+    fragment::{Marker, MarkerLine},
+    map::{is_horizontal_line, is_vertical_line},
+    Cell, Fragment,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The endings of the relationships in UML class diagrams,
+/// written at either end of a horizontal line.
+///
+/// ```ignore
+///     <|------|>    inheritance
+///     <>------<>    aggregation
+///     <*>----<*>    composition
+/// ```
+///
+/// Each entry is the ending at the left end of the line, the ending at the right end
+/// of the line, both as they are read from left to right, and the marker they are drawn as.
+const UML_ENDINGS: [(&str, &str, Marker); 3] = [
+    ("<|", "|>", Marker::ClearArrow),
+    ("<>", "<>", Marker::OpenDiamond),
+    ("<*>", "<*>", Marker::FilledDiamond),
+];
+
+/// The aggregation and composition endings written as a single character.
+/// These are only endings when they are attached to the side of a box,
+/// otherwise `o` and `*` at the end of a line are drawn as circles.
+///
+/// ```ignore
+///     +---+         +---+
+///     | A |o--------| B |    aggregation
+///     |   |*--------|   |    composition
+///     +---+         +---+
+/// ```
+const ATTACHED_UML_ENDINGS: [(&str, &str, Marker); 2] = [
+    ("o", "o", Marker::OpenDiamond),
+    ("*", "*", Marker::FilledDiamond),
+];
+
+/// The crow's foot endings of entity relationship diagrams,
+/// written the same way as the `UML_ENDINGS`.
+///
+/// ```ignore
+///     ||------||    one
+///     >-------<     many
+///     |o------o|    zero or one
+///     >|------|<    one or many
+///     >o------o<    zero or many
+/// ```
+const CROWS_FEET: [(&str, &str, Marker); 5] = [
+    ("||", "||", Marker::One),
+    (">", "<", Marker::Many),
+    ("|o", "o|", Marker::ZeroOrOne),
+    (">|", "|<", Marker::OneOrMany),
+    (">o", "o<", Marker::ZeroOrMany),
+];
+
+/// the characters which continues a vertical line,
+/// a `|` next to these is a side of a box rather than part of an ending
+fn is_box_side(ch: Option<&char>) -> bool {
+    is_vertical_line(ch)
+        || matches!(
+            ch,
+            Some('+') | Some('.') | Some('\'') | Some(':') | Some('!')
+        )
+}
+
+/// Find the UML endings and crow's feet at the ends of the horizontal lines in this span.
+///
+/// returns the marker lines of the matched endings, each one is attached to the cell
+/// next to the line and localized to that cell, and the cells that are not part of
+/// any ending
+pub(crate) fn endorse_endings(
+    span: &Span,
+) -> (Vec<(Cell, FragmentSpan)>, Span) {
+    let cells: BTreeMap<Cell, char> = span.iter().copied().collect();
+    let mut matched: BTreeSet<Cell> = BTreeSet::new();
+    let mut endings = vec![];
+
+    for (cell, ch) in cells.iter() {
+        if !is_horizontal_line(Some(ch)) {
+            continue;
+        }
+        // the UML endings goes first, since `<>` would otherwise be a crow's foot
+        for (left_end, right_end, marker) in
+            UML_ENDINGS.iter().chain(CROWS_FEET.iter())
+        {
+            // the right end is read away from the line, while the left end is read
+            // towards the line
+            let line_ends = [
+                (1, right_end.chars().collect::<Vec<_>>()),
+                (-1, left_end.chars().rev().collect::<Vec<_>>()),
+            ];
+            for (direction, ending) in line_ends {
+                if let Some((inner, frag_span)) = match_ending(
+                    &cells, &matched, *cell, direction, &ending, marker, false,
+                ) {
+                    matched.extend(frag_span.cells());
+                    endings.push((inner, frag_span));
+                }
+            }
+        }
+    }
+    let unmatched = span
+        .iter()
+        .filter(|(cell, _ch)| !matched.contains(cell))
+        .copied()
+        .collect::<Vec<_>>();
+    (endings, Span::from(unmatched))
+}
+
+/// Find the aggregation and composition endings attached to the side of a box.
+///
+/// This is done on the span before the boxes are taken out of it, since the
+/// box sides are needed to tell these endings apart from the circles.
+/// The crow's feet which ends in `o` are matched first, and are left in the span.
+///
+/// returns the marker lines of the matched endings in absolute position,
+/// and the cells that are not part of any of these endings
+pub(crate) fn endorse_attached_endings(
+    span: &Span,
+) -> (Vec<FragmentSpan>, Span) {
+    let (crows_feet, _) = endorse_endings(span);
+    let crows_feet_cells: BTreeSet<Cell> = crows_feet
+        .iter()
+        .flat_map(|(_, frag_span)| frag_span.cells())
+        .collect();
+    let cells: BTreeMap<Cell, char> = span.iter().copied().collect();
+    let mut matched: BTreeSet<Cell> = crows_feet_cells.clone();
+    let mut endings = vec![];
+
+    for (cell, ch) in cells.iter() {
+        if !is_horizontal_line(Some(ch)) {
+            continue;
+        }
+        for (left_end, right_end, marker) in ATTACHED_UML_ENDINGS.iter() {
+            let line_ends = [(1, right_end), (-1, left_end)];
+            for (direction, ending) in line_ends {
+                let ending: Vec<char> = ending.chars().collect();
+                if let Some((inner, frag_span)) = match_ending(
+                    &cells, &matched, *cell, direction, &ending, marker, true,
+                ) {
+                    matched.extend(frag_span.cells());
+                    endings.push(frag_span.absolute_position(inner));
+                }
+            }
+        }
+    }
+    let unmatched = span
+        .iter()
+        .filter(|(cell, _ch)| {
+            !matched.contains(cell) || crows_feet_cells.contains(cell)
+        })
+        .copied()
+        .collect::<Vec<_>>();
+    (endings, Span::from(unmatched))
+}
+
+/// the ending is at the end of the line when nothing follows it on the far side,
+/// or when it touches the side of a box.
+/// Anything else after it, such as more of the line or the corner of a shape,
+/// means the ending is a `<` or `>` in the middle of the drawing.
+///
+/// ```ignore
+///     ||------o<     +---+
+///                    |   |>o-----||
+///     ->------       +---+
+/// ```
+fn is_line_end(
+    cells: &BTreeMap<Cell, char>,
+    outer: Cell,
+    direction: i32,
+) -> bool {
+    let far = cells.get(&Cell::new(outer.x + direction, outer.y));
+    far.is_none() || is_vertical_line(far) || far == Some(&'+')
+}
+
+/// match the ending next to the line at `line_cell`, going to the `direction`.
+/// The ending must be at the end of the line, and `|` must not be part of a vertical line.
+/// When `is_attached`, the ending must be followed by the side of a box.
+///
+/// returns the cell next to the line and the marker line localized to that cell
+fn match_ending(
+    cells: &BTreeMap<Cell, char>,
+    matched: &BTreeSet<Cell>,
+    line_cell: Cell,
+    direction: i32,
+    ending: &[char],
+    marker: &Marker,
+    is_attached: bool,
+) -> Option<(Cell, FragmentSpan)> {
+    let ending_cells: Vec<(Cell, char)> = ending
+        .iter()
+        .enumerate()
+        .map(|(i, ch)| {
+            (
+                Cell::new(
+                    line_cell.x + direction * (i as i32 + 1),
+                    line_cell.y,
+                ),
+                *ch,
+            )
+        })
+        .collect();
+    let (inner, _) = ending_cells[0];
+    let (outer, _) = ending_cells[ending_cells.len() - 1];
+
+    let is_matched = ending_cells.iter().all(|(cell, ch)| {
+        cells.get(cell) == Some(ch)
+            && !matched.contains(cell)
+            && (*ch != '|'
+                || !(is_box_side(cells.get(&cell.top()))
+                    || is_box_side(cells.get(&cell.bottom()))))
+    }) && if is_attached {
+        is_box_side(cells.get(&Cell::new(outer.x + direction, outer.y)))
+    } else {
+        is_line_end(cells, outer, direction)
+    };
+
+    if is_matched {
+        // the line goes from the side facing the line to the far side of the outer cell
+        let offset = Cell::new(outer.x - inner.x, 0);
+        let (start, end) = if direction > 0 {
+            (CellGrid::k(), offset.absolute_position(CellGrid::o()))
+        } else {
+            (CellGrid::o(), offset.absolute_position(CellGrid::k()))
+        };
+        let marker_line =
+            MarkerLine::new(start, end, false, None, Some(marker.clone()));
+        Some((
+            inner,
+            FragmentSpan::new(
+                Span::from(ending_cells),
+                Fragment::MarkerLine(marker_line),
+            ),
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::CellBuffer;
+
+    #[test]
+    fn test_crows_feet() {
+        let art = r#"
+    >|------o<
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (crows_feet, unmatched) = endorse_endings(&span1);
+        assert_eq!(crows_feet.len(), 2);
+        assert_eq!(unmatched.len(), 6);
+        let markers: Vec<Marker> = crows_feet
+            .iter()
+            .filter_map(|(_, frag_span)| {
+                frag_span.fragment.as_marker_line()?.end_marker.clone()
+            })
+            .collect();
+        assert!(markers.contains(&Marker::OneOrMany));
+        assert!(markers.contains(&Marker::ZeroOrMany));
+    }
+
+    #[test]
+    fn test_box_side_is_not_crows_foot() {
+        let art = r#"
+    +---+
+    |   ||-----
+    +---+
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (crows_feet, _unmatched) = endorse_endings(&span1);
+        assert!(crows_feet.is_empty());
+    }
+
+    #[test]
+    fn test_arrow_in_line_is_not_crows_foot() {
+        let art = r#"
+    ->--------
+
+    |->--+
+
+         |`>   <'|
+   ------<'     `>----
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let spans: Vec<Span> = (&cell_buffer).into();
+        for span in spans {
+            let (crows_feet, _unmatched) = endorse_endings(&span);
+            assert!(crows_feet.is_empty(), "{:#?}", crows_feet);
+        }
+    }
+
+    #[test]
+    fn test_attached_uml_endings() {
+        let art = r#"
+    +---+         +---+
+    | A |o--------| B |
+    |   |*--------|   |
+    +---+         +---+
+
+    o--------o
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let spans: Vec<Span> = (&cell_buffer).into();
+        let markers: Vec<Marker> = spans
+            .iter()
+            .flat_map(|span| endorse_attached_endings(span).0)
+            .filter_map(|frag_span| {
+                frag_span.fragment.as_marker_line()?.end_marker.clone()
+            })
+            .collect();
+        assert_eq!(markers, vec![Marker::OpenDiamond, Marker::FilledDiamond]);
+    }
+
+    #[test]
+    fn test_attached_to_box_drawing_side() {
+        let art = r#"
+    ┌───┐
+    │ A │o--------
+    └───┘
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let spans: Vec<Span> = (&cell_buffer).into();
+        let markers: Vec<Marker> = spans
+            .iter()
+            .flat_map(|span| endorse_attached_endings(span).0)
+            .filter_map(|frag_span| {
+                frag_span.fragment.as_marker_line()?.end_marker.clone()
+            })
+            .collect();
+        assert_eq!(markers, vec![Marker::OpenDiamond]);
+    }
+}
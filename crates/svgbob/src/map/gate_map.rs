@@ -0,0 +1,295 @@
+use crate::{
+    buffer::{FragmentSpan, Span},
+    fragment::{Gate, GateKind},
+    map::is_horizontal_line,
+    Cell, Fragment, Point,
+};
+use once_cell::sync::Lazy;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The logic gate templates.
+/// The gates have to match these exactly, so only these sizes are recognized.
+/// (
+/// art - the ascii art of the gate, the whitespace inside the gate can contain anything
+///     such as the name of the gate
+/// GateKind - the kind of gate drawn
+/// f32 - the x location of the back of the gate, relative to the left most cell
+/// )
+static GATE_ART_MAP: Lazy<Vec<(&'static str, GateKind, f32)>> =
+    Lazy::new(|| {
+        vec![
+            (
+                r#"
+            .--.
+            |   \
+            |    )
+            |   /
+            '--'
+            "#,
+                GateKind::And,
+                0.5,
+            ),
+            (
+                r#"
+             .--.
+            \\   \
+             ))   >
+            //   /
+             '--'
+            "#,
+                GateKind::Xor,
+                1.0,
+            ),
+            (
+                r#"
+            .--.
+            \   \
+             )   >
+            /   /
+            '--'
+            "#,
+                GateKind::Or,
+                0.0,
+            ),
+            (
+                r#"
+            |\
+            | >o
+            |/
+            "#,
+                GateKind::Not,
+                0.5,
+            ),
+        ]
+    });
+
+/// The gate templates, localized to the top-left of the template
+static GATE_TEMPLATES: Lazy<Vec<GateTemplate>> = Lazy::new(|| {
+    GATE_ART_MAP
+        .iter()
+        .map(|(art, kind, back_x)| GateTemplate::new(art, *kind, *back_x))
+        .collect()
+});
+
+struct GateTemplate {
+    cells: BTreeMap<Cell, char>,
+    /// the gate localized to the top-left of the template
+    gate: Gate,
+}
+
+impl GateTemplate {
+    fn new(art: &str, kind: GateKind, back_x: f32) -> Self {
+        let art_cells: Vec<(Cell, char)> = art
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars().enumerate().filter_map(move |(x, ch)| {
+                    if ch.is_whitespace() {
+                        None
+                    } else {
+                        Some((Cell::new(x as i32, y as i32), ch))
+                    }
+                })
+            })
+            .collect();
+        let min_x = art_cells.iter().map(|(cell, _)| cell.x).min().unwrap();
+        let min_y = art_cells.iter().map(|(cell, _)| cell.y).min().unwrap();
+        let top_left = Cell::new(min_x, min_y);
+        let cells: BTreeMap<Cell, char> = art_cells
+            .into_iter()
+            .map(|(cell, ch)| (top_left.localize_cell(cell), ch))
+            .collect();
+        let max_x = cells.keys().map(|cell| cell.x).max().unwrap();
+        let max_y = cells.keys().map(|cell| cell.y).max().unwrap();
+        // the body goes from the middle of the top row to the middle of the bottom row
+        let start = Point::new(back_x, Cell::new(0, 0).m().y);
+        let end =
+            Point::new(Cell::new(max_x, 0).o().x, Cell::new(0, max_y).m().y);
+        GateTemplate {
+            cells,
+            gate: Gate::new(kind, start, end, vec![]),
+        }
+    }
+
+    /// the left most cell of the template on each of the rows in between
+    /// the top and bottom row, which is where the input wires are connected
+    fn input_cells(&self) -> Vec<Cell> {
+        let max_y = self.cells.keys().map(|cell| cell.y).max().unwrap_or(0);
+        (1..max_y)
+            .filter_map(|y| self.cells.keys().find(|cell| cell.y == y).copied())
+            .collect()
+    }
+
+    /// match the template with its top-left at `loc`
+    fn is_match(
+        &self,
+        cells: &BTreeMap<Cell, char>,
+        matched: &BTreeSet<Cell>,
+        loc: Cell,
+    ) -> bool {
+        self.cells.iter().all(|(local, ch)| {
+            let cell = Cell::new(loc.x + local.x, loc.y + local.y);
+            cells.get(&cell) == Some(ch) && !matched.contains(&cell)
+        })
+    }
+
+    /// the gate at `loc`, with the inputs where there are wires connected to it.
+    /// The gate is localized to `loc`
+    fn gate_at(&self, cells: &BTreeMap<Cell, char>, loc: Cell) -> Gate {
+        let inputs = self
+            .input_cells()
+            .into_iter()
+            .filter(|local| {
+                let wire = Cell::new(loc.x + local.x - 1, loc.y + local.y);
+                is_horizontal_line(cells.get(&wire))
+            })
+            .map(|local| local.k())
+            .collect();
+        Gate {
+            inputs,
+            ..self.gate.clone()
+        }
+    }
+}
+
+/// Find the logic gates in this span.
+///
+/// returns the gates, each one is attached to the top-left of the gate's template
+/// and localized to that cell, and the cells that are not part of any gate
+pub(crate) fn endorse_gates(span: &Span) -> (Vec<(Cell, FragmentSpan)>, Span) {
+    let cells: BTreeMap<Cell, char> = span.iter().copied().collect();
+    let mut matched: BTreeSet<Cell> = BTreeSet::new();
+    let mut gates = vec![];
+
+    for template in GATE_TEMPLATES.iter() {
+        let (anchor, anchor_ch) = match template.cells.iter().next() {
+            Some((anchor, ch)) => (*anchor, *ch),
+            None => continue,
+        };
+        let candidates: Vec<Cell> = cells
+            .iter()
+            .filter(|(_cell, ch)| **ch == anchor_ch)
+            .map(|(cell, _ch)| Cell::new(cell.x - anchor.x, cell.y - anchor.y))
+            .collect();
+        for loc in candidates {
+            if template.is_match(&cells, &matched, loc) {
+                let body: Vec<(Cell, char)> = template
+                    .cells
+                    .iter()
+                    .map(|(local, ch)| {
+                        (Cell::new(loc.x + local.x, loc.y + local.y), *ch)
+                    })
+                    .collect();
+                matched.extend(body.iter().map(|(cell, _)| *cell));
+                let gate = template.gate_at(&cells, loc);
+                gates.push((
+                    loc,
+                    FragmentSpan::new(Span::from(body), Fragment::Gate(gate)),
+                ));
+            }
+        }
+    }
+    let unmatched = span
+        .iter()
+        .filter(|(cell, _ch)| !matched.contains(cell))
+        .copied()
+        .collect::<Vec<_>>();
+    (gates, Span::from(unmatched))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::CellBuffer;
+
+    #[test]
+    fn test_and_gate() {
+        let art = r#"
+       .--.
+    ---|   \
+       | &  )---
+    ---|   /
+       '--'
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (gates, unmatched) = endorse_gates(&span1);
+        assert_eq!(gates.len(), 1);
+        // the wires are not part of the gate,
+        // while the label is not even connected to it
+        assert_eq!(unmatched.len(), 9);
+        let gate = match &gates[0].1.fragment {
+            Fragment::Gate(gate) => gate.clone(),
+            _ => unreachable!(),
+        };
+        assert_eq!(gate.kind, GateKind::And);
+        assert_eq!(gate.start, Point::new(0.5, 1.0));
+        assert_eq!(gate.end, Point::new(6.0, 9.0));
+        assert_eq!(gate.output(), Point::new(6.0, 5.0));
+        assert_eq!(
+            gate.inputs,
+            vec![Point::new(0.0, 3.0), Point::new(0.0, 7.0)]
+        );
+    }
+
+    #[test]
+    fn test_xor_is_not_or() {
+        let art = r#"
+       .--.
+    --\\   \
+       ))   >---
+    --//   /
+       '--'
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (gates, _unmatched) = endorse_gates(&span1);
+        assert_eq!(gates.len(), 1);
+        assert!(matches!(
+            &gates[0].1.fragment,
+            Fragment::Gate(gate) if gate.kind == GateKind::Xor
+        ));
+    }
+
+    #[test]
+    fn test_other_sizes_are_not_gates() {
+        let arts = [
+            // wider
+            r#"
+       .----.
+    ---|     \
+       |      )---
+    ---|     /
+       '----'
+            "#,
+            // taller
+            r#"
+       .--.
+    ---\   \
+        \   \
+         )   >---
+        /   /
+    ---/   /
+       '--'
+            "#,
+            // taller, for the 3 inputs
+            r#"
+        |\
+     ---| \
+     ---|  >o---
+     ---| /
+        |/
+            "#,
+        ];
+        for art in arts {
+            let cell_buffer = CellBuffer::from(art);
+            let spans: Vec<Span> = (&cell_buffer).into();
+            for span in spans {
+                let (gates, _unmatched) = endorse_gates(&span);
+                assert!(gates.is_empty(), "not a gate: {art}");
+            }
+        }
+    }
+}
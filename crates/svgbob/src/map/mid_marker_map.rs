@@ -0,0 +1,180 @@
+use crate::{
+    buffer::{CellGrid, FragmentSpan, Span},
+    fragment::{Marker, MarkerLine, MidMarker},
+    map::{is_horizontal_line, is_vertical_line},
+    Cell, Fragment, Point,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The arrowheads that can be placed in the middle of a line,
+/// with the direction of the line they are placed on and the point of the cell
+/// at their base and tip.
+///
+/// ```ignore
+///     ---->>----   ----<<----     |
+///                                 v
+///                                 |
+/// ```
+fn arrowhead(ch: char) -> Option<(Direction, Point, Point)> {
+    match ch {
+        '>' => Some((Direction::Horizontal, CellGrid::k(), CellGrid::o())),
+        '<' => Some((Direction::Horizontal, CellGrid::o(), CellGrid::k())),
+        'v' | 'V' => Some((Direction::Vertical, CellGrid::h(), CellGrid::r())),
+        '^' => Some((Direction::Vertical, CellGrid::r(), CellGrid::h())),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Horizontal,
+    Vertical,
+}
+
+impl Direction {
+    fn next(&self, cell: Cell) -> Cell {
+        match self {
+            Direction::Horizontal => cell.right(),
+            Direction::Vertical => cell.bottom(),
+        }
+    }
+
+    fn prev(&self, cell: Cell) -> Cell {
+        match self {
+            Direction::Horizontal => cell.left(),
+            Direction::Vertical => cell.top(),
+        }
+    }
+
+    fn is_line(&self, ch: Option<&char>) -> bool {
+        match self {
+            Direction::Horizontal => is_horizontal_line(ch),
+            Direction::Vertical => is_vertical_line(ch),
+        }
+    }
+
+    /// the end points of the line going through a cell
+    fn line_ends(&self) -> (Point, Point) {
+        match self {
+            Direction::Horizontal => (CellGrid::k(), CellGrid::o()),
+            Direction::Vertical => (CellGrid::c(), CellGrid::w()),
+        }
+    }
+}
+
+/// Find the runs of arrowheads in the middle of the lines in this span.
+///
+/// The line must continue for at least 2 characters on both sides of the arrowheads,
+/// an arrowhead next to the end of a line such as `->----` is drawn as the usual arrow.
+///
+/// returns the marker lines going through each run of arrowheads, attached to the first
+/// cell of the run and localized to that cell, and the cells that are not part of
+/// any run
+pub(crate) fn endorse_mid_markers(
+    span: &Span,
+) -> (Vec<(Cell, FragmentSpan)>, Span) {
+    let cells: BTreeMap<Cell, char> = span.iter().copied().collect();
+    let mut matched: BTreeSet<Cell> = BTreeSet::new();
+    let mut mid_markers = vec![];
+
+    for (cell, ch) in cells.iter() {
+        if matched.contains(cell) {
+            continue;
+        }
+        if let Some(frag_span) = match_arrowheads(&cells, *cell, *ch) {
+            matched.extend(frag_span.cells());
+            mid_markers.push((*cell, frag_span));
+        }
+    }
+    let unmatched = span
+        .iter()
+        .filter(|(cell, _ch)| !matched.contains(cell))
+        .copied()
+        .collect::<Vec<_>>();
+    (mid_markers, Span::from(unmatched))
+}
+
+/// match a run of the same arrowhead character starting at this cell,
+/// in between 2 lines going the same direction as the arrowhead
+fn match_arrowheads(
+    cells: &BTreeMap<Cell, char>,
+    first: Cell,
+    ch: char,
+) -> Option<FragmentSpan> {
+    let (direction, base, tip) = arrowhead(ch)?;
+    let has_line_at = |cell: Cell| direction.is_line(cells.get(&cell));
+
+    let before = direction.prev(first);
+    if !has_line_at(before) || !has_line_at(direction.prev(before)) {
+        return None;
+    }
+    let mut run = vec![(first, ch)];
+    let mut last = first;
+    while cells.get(&direction.next(last)) == Some(&ch) {
+        last = direction.next(last);
+        run.push((last, ch));
+    }
+    let after = direction.next(last);
+    if !has_line_at(after) || !has_line_at(direction.next(after)) {
+        return None;
+    }
+
+    let localize = |cell: Cell, p: Point| {
+        Cell::new(cell.x - first.x, cell.y - first.y).absolute_position(p)
+    };
+    let arrowheads = run
+        .iter()
+        .map(|(cell, _)| {
+            MidMarker::new(
+                localize(*cell, base),
+                localize(*cell, tip),
+                Marker::Arrow,
+            )
+        })
+        .collect();
+    let (start, end) = direction.line_ends();
+    let marker_line =
+        MarkerLine::new(start, localize(last, end), false, None, None)
+            .with_mid_markers(arrowheads);
+    Some(FragmentSpan::new(
+        Span::from(run),
+        Fragment::MarkerLine(marker_line),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::CellBuffer;
+
+    #[test]
+    fn test_double_arrow() {
+        let art = r#"
+    ---->>-------
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (mid_markers, unmatched) = endorse_mid_markers(&span1);
+        assert_eq!(mid_markers.len(), 1);
+        assert_eq!(unmatched.len(), 11);
+        let (cell, frag_span) = &mid_markers[0];
+        assert_eq!(*cell, Cell::new(8, 1));
+        let mline = frag_span.fragment.as_marker_line().unwrap();
+        assert_eq!(mline.mid_markers.len(), 2);
+        assert_eq!(mline.line.start, CellGrid::k());
+        assert_eq!(mline.line.end, Point::new(2.0, 1.0));
+    }
+
+    #[test]
+    fn test_crows_foot_stub_is_not_mid_marker() {
+        let art = r#"
+    |->-------
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (mid_markers, _unmatched) = endorse_mid_markers(&span1);
+        assert!(mid_markers.is_empty());
+    }
+}
@@ -0,0 +1,458 @@
+use crate::{
+    buffer::{CellGrid, FragmentSpan, Span},
+    fragment::{DashStyle, Lifeline, Marker, MarkerLine, Message, Text},
+    map::{is_horizontal_line, is_vertical_line},
+    Cell, Fragment, Point,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// the dash style of the lifeline drawn with this character
+fn lifeline_dash(ch: Option<&char>) -> Option<DashStyle> {
+    match ch {
+        Some(':') => Some(DashStyle::Dotted),
+        Some('┆') => Some(DashStyle::Dashed),
+        Some('|') => Some(DashStyle::Solid),
+        _ => None,
+    }
+}
+
+fn is_box_corner(ch: Option<&char>) -> bool {
+    matches!(
+        ch,
+        Some('+') | Some('.') | Some('\'') | Some('└') | Some('┘')
+    )
+}
+
+/// the characters that make up a message arrow,
+/// these are also the characters where a message crosses a lifeline
+fn is_message_char(ch: Option<&char>) -> bool {
+    matches!(
+        ch,
+        Some('-') | Some('─') | Some('+') | Some('<') | Some('>')
+    )
+}
+
+/// A lifeline found in the span, in cell coordinates
+struct LifelineCells {
+    column: i32,
+    /// the row of the first cell of the lifeline
+    top: i32,
+    /// the row of the last cell of the lifeline
+    bottom: i32,
+    dash: DashStyle,
+    /// the top and bottom row of the activation bars
+    activations: Vec<(i32, i32)>,
+    /// the cells of the lifeline and its activation bars,
+    /// excluding the cells where messages cross it
+    cells: Vec<(Cell, char)>,
+}
+
+impl LifelineCells {
+    fn is_activated(&self, y: i32) -> bool {
+        self.activations
+            .iter()
+            .any(|(top, bottom)| y > *top && y < *bottom)
+    }
+
+    /// the column where the lifeline or its activation bar is drawn
+    /// on the `left` or right side of the lifeline at row `y`
+    fn edge(&self, y: i32, left: bool) -> i32 {
+        match (self.is_activated(y), left) {
+            (true, true) => self.column - 1,
+            (true, false) => self.column + 1,
+            (false, _) => self.column,
+        }
+    }
+
+    /// check if the end of a message at `x` on row `y` is attached to this
+    /// lifeline, from the `left` or right side of the lifeline
+    fn is_attached(&self, x: i32, y: i32, left: bool) -> bool {
+        let outside = if left {
+            self.edge(y, true) - 1
+        } else {
+            self.edge(y, false) + 1
+        };
+        y >= self.top && y <= self.bottom && (x == outside || x == self.column)
+    }
+
+    fn into_fragment_span(self) -> (Cell, FragmentSpan) {
+        let first = Cell::new(self.column, self.top);
+        let local = |cell: Cell, p: Point| {
+            first.localize_cell(cell).absolute_position(p)
+        };
+        // the lifeline starts at the bottom of the participant box
+        let start = local(Cell::new(self.column, self.top - 1), CellGrid::m());
+        let last = Cell::new(self.column, self.bottom);
+        let end = if self.cells.iter().any(|(cell, _)| *cell == last) {
+            local(last, CellGrid::w())
+        } else {
+            local(last, CellGrid::m())
+        };
+        let activations = self
+            .activations
+            .iter()
+            .map(|(top, bottom)| {
+                (
+                    local(Cell::new(self.column - 1, *top), CellGrid::m()),
+                    local(Cell::new(self.column + 1, *bottom), CellGrid::m()),
+                )
+            })
+            .collect();
+        let lifeline = Lifeline::new(start, end, self.dash, activations);
+        (
+            first,
+            FragmentSpan::new(
+                Span::from(self.cells),
+                Fragment::Lifeline(lifeline),
+            ),
+        )
+    }
+}
+
+/// Find the sequence diagrams in this span.
+///
+/// ```ignore
+///   +-------+         +-----+
+///   | Alice |         | Bob |
+///   +-------+         +-----+
+///       :                :
+///       :     hello      :
+///       +--------------->:
+///       :               +-+
+///       :     call      | |
+///       +-------------->| |
+///       :               +-+
+/// ```
+/// A lifeline is a vertical `:`, `|` or `┆` hanging from the bottom of a
+/// participant box, it may be crossed by messages and may have activation bars
+/// on it. A message is a horizontal arrow from one lifeline to another, with
+/// its label directly above it.
+/// Only the lifelines which has messages attached to them are taken.
+///
+/// returns the lifelines, each one is attached to its top most cell,
+/// the messages, each one is attached to its left most cell,
+/// and the cells that are not part of the sequence diagram
+pub(crate) fn endorse_sequences(
+    span: &Span,
+) -> (Vec<(Cell, FragmentSpan)>, Span) {
+    let cells: BTreeMap<Cell, char> = span.iter().copied().collect();
+    let mut lifelines: Vec<LifelineCells> = vec![];
+    for cell in cells.keys() {
+        // the lifeline continuing below an activation bar is already
+        // part of the lifeline above it
+        let is_taken = lifelines.iter().any(|lifeline| {
+            lifeline.column == cell.x
+                && cell.y >= lifeline.top
+                && cell.y <= lifeline.bottom
+        });
+        if !is_taken {
+            lifelines.extend(match_lifeline(&cells, *cell));
+        }
+    }
+    if lifelines.is_empty() {
+        return (vec![], span.clone());
+    }
+    let lifeline_cells: BTreeSet<Cell> = lifelines
+        .iter()
+        .flat_map(|lifeline| lifeline.cells.iter().map(|(cell, _)| *cell))
+        .collect();
+
+    let runs = message_runs(&cells, &lifeline_cells);
+    let run_cells: BTreeSet<Cell> = runs.iter().flatten().copied().collect();
+
+    let mut matched: BTreeSet<Cell> = BTreeSet::new();
+    let mut attached: BTreeSet<usize> = BTreeSet::new();
+    let mut fragments = vec![];
+    for run in runs.iter() {
+        let first = run[0];
+        let last = run[run.len() - 1];
+        let y = first.y;
+        let from = lifelines
+            .iter()
+            .position(|lifeline| lifeline.is_attached(first.x, y, false));
+        let to = lifelines
+            .iter()
+            .position(|lifeline| lifeline.is_attached(last.x, y, true));
+        let (from, to) = match (from, to) {
+            (Some(from), Some(to)) if from != to => (from, to),
+            _ => continue,
+        };
+        let start_marker =
+            (cells.get(&first) == Some(&'<')).then_some(Marker::Arrow);
+        let end_marker =
+            (cells.get(&last) == Some(&'>')).then_some(Marker::Arrow);
+        if start_marker.is_none() && end_marker.is_none() {
+            continue;
+        }
+        let local = |cell: Cell, p: Point| {
+            first.localize_cell(cell).absolute_position(p)
+        };
+        let from_x = lifelines[from].edge(y, false);
+        let to_x = lifelines[to].edge(y, true);
+        let line = MarkerLine::new(
+            local(Cell::new(from_x, y), CellGrid::m()),
+            local(Cell::new(to_x, y), CellGrid::m()),
+            false,
+            start_marker,
+            end_marker,
+        );
+        let label_cells: Vec<(Cell, char)> = ((from_x + 1)..to_x)
+            .map(|x| Cell::new(x, y - 1))
+            .filter(|cell| {
+                !lifeline_cells.contains(cell) && !run_cells.contains(cell)
+            })
+            .filter_map(|cell| cells.get(&cell).map(|ch| (cell, *ch)))
+            .collect();
+        let label = label_cells.first().map(|(label_start, _)| {
+            let label_end = label_cells[label_cells.len() - 1].0;
+            let text: String = (label_start.x..=label_end.x)
+                .map(|x| *cells.get(&Cell::new(x, y - 1)).unwrap_or(&' '))
+                .collect();
+            Text::new(local(*label_start, CellGrid::q()), text)
+        });
+        let message_cells: Vec<(Cell, char)> = run
+            .iter()
+            .map(|cell| (*cell, cells[cell]))
+            .chain(label_cells)
+            .collect();
+        matched.extend(message_cells.iter().map(|(cell, _)| *cell));
+        attached.insert(from);
+        attached.insert(to);
+        fragments.push((
+            first,
+            FragmentSpan::new(
+                Span::from(message_cells),
+                Fragment::Message(Message::new(line, label)),
+            ),
+        ));
+    }
+    for (index, lifeline) in lifelines.into_iter().enumerate() {
+        if attached.contains(&index) {
+            matched.extend(lifeline.cells.iter().map(|(cell, _)| *cell));
+            fragments.push(lifeline.into_fragment_span());
+        }
+    }
+    let unmatched = span
+        .iter()
+        .filter(|(cell, _ch)| !matched.contains(cell))
+        .copied()
+        .collect::<Vec<_>>();
+    (fragments, Span::from(unmatched))
+}
+
+/// check if the cell is in the middle of the bottom side of a box,
+/// which is where the lifeline of a participant starts
+fn is_box_bottom(cells: &BTreeMap<Cell, char>, cell: Cell) -> bool {
+    let corner = |next: fn(&Cell) -> Cell| {
+        let mut current = cell;
+        while is_horizontal_line(cells.get(&current)) {
+            current = next(&current);
+        }
+        is_box_corner(cells.get(&current))
+            && is_vertical_line(cells.get(&current.top()))
+    };
+    is_horizontal_line(cells.get(&cell))
+        && corner(|cell| cell.left())
+        && corner(|cell| cell.right())
+}
+
+/// the bottom row of the activation bar whose top is at `top`,
+/// the activation bar is centered on the lifeline
+///
+/// ```ignore
+///     +-+
+///     | |
+///     +-+
+/// ```
+fn activation_bottom(
+    cells: &BTreeMap<Cell, char>,
+    column: i32,
+    top: i32,
+) -> Option<i32> {
+    let is_cap = |y: i32| {
+        cells.get(&Cell::new(column - 1, y)) == Some(&'+')
+            && is_horizontal_line(cells.get(&Cell::new(column, y)))
+            && cells.get(&Cell::new(column + 1, y)) == Some(&'+')
+    };
+    if !is_cap(top) {
+        return None;
+    }
+    let mut y = top + 1;
+    while is_vertical_line(cells.get(&Cell::new(column - 1, y)))
+        && is_vertical_line(cells.get(&Cell::new(column + 1, y)))
+    {
+        y += 1;
+    }
+    (y > top + 1 && is_cap(y)).then_some(y)
+}
+
+/// match the lifeline which starts at this cell
+fn match_lifeline(
+    cells: &BTreeMap<Cell, char>,
+    first: Cell,
+) -> Option<LifelineCells> {
+    let dash = lifeline_dash(cells.get(&first))?;
+    if !is_box_bottom(cells, first.top()) {
+        return None;
+    }
+    let column = first.x;
+    let mut lifeline_cells = vec![];
+    let mut activations = vec![];
+    let mut bottom = first.y;
+    let mut y = first.y;
+    loop {
+        let cell = Cell::new(column, y);
+        let ch = cells.get(&cell);
+        if lifeline_dash(ch).is_some() {
+            lifeline_cells.push((cell, *ch?));
+        } else if let Some(activation_end) = activation_bottom(cells, column, y)
+        {
+            // the caps and the sides of the activation bar, and the lifeline
+            // if it is drawn inside of it
+            for row in y..=activation_end {
+                let is_cap = row == y || row == activation_end;
+                for x in (column - 1)..=(column + 1) {
+                    let bar_cell = Cell::new(x, row);
+                    if let Some(bar_ch) = cells.get(&bar_cell) {
+                        if is_cap
+                            || x != column
+                            || lifeline_dash(Some(bar_ch)).is_some()
+                        {
+                            lifeline_cells.push((bar_cell, *bar_ch));
+                        }
+                    }
+                }
+            }
+            activations.push((y, activation_end));
+            y = activation_end;
+        } else if !is_message_char(ch) {
+            break;
+        }
+        bottom = y;
+        y += 1;
+    }
+    Some(LifelineCells {
+        column,
+        top: first.y,
+        bottom,
+        dash,
+        activations,
+        cells: lifeline_cells,
+    })
+}
+
+/// the horizontal runs of message characters which are not part of the lifelines.
+/// The arrowheads can only be at the ends of the run.
+fn message_runs(
+    cells: &BTreeMap<Cell, char>,
+    lifeline_cells: &BTreeSet<Cell>,
+) -> Vec<Vec<Cell>> {
+    let is_run_cell = |cell: &Cell| {
+        is_message_char(cells.get(cell)) && !lifeline_cells.contains(cell)
+    };
+    cells
+        .keys()
+        .filter(|cell| is_run_cell(cell) && !is_run_cell(&cell.left()))
+        .filter_map(|first| {
+            let mut run = vec![*first];
+            let mut current = first.right();
+            while is_run_cell(&current) {
+                run.push(current);
+                current = current.right();
+            }
+            if run.len() < 3 {
+                return None;
+            }
+            let has_line =
+                run.iter().any(|cell| is_horizontal_line(cells.get(cell)));
+            let inner = &run[1..run.len() - 1];
+            let has_inner_arrowhead = inner
+                .iter()
+                .any(|cell| matches!(cells.get(cell), Some('<') | Some('>')));
+            (has_line && !has_inner_arrowhead).then_some(run)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::buffer::CellBuffer;
+
+    #[test]
+    fn test_messages_and_lifelines() {
+        let art = r#"
+    +-------+       +-----+
+    | Alice |       | Bob |
+    +-------+       +-----+
+        :              :
+        :    hello     :
+        +------------->:
+        :              :
+        :<-------------+
+        :              :
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (fragments, _unmatched) = endorse_sequences(&span1);
+        let messages: Vec<&Message> = fragments
+            .iter()
+            .filter_map(|(_, frag_span)| match &frag_span.fragment {
+                Fragment::Message(message) => Some(message),
+                _ => None,
+            })
+            .collect();
+        let lifelines = fragments
+            .iter()
+            .filter(|(_, frag_span)| {
+                matches!(frag_span.fragment, Fragment::Lifeline(_))
+            })
+            .count();
+        assert_eq!(lifelines, 2);
+        assert_eq!(messages.len(), 2);
+        assert_eq!(
+            messages[0].label.as_ref().map(|label| label.text.as_str()),
+            Some("hello")
+        );
+        assert_eq!(messages[0].line.end_marker, Some(Marker::Arrow));
+        assert!(messages[1].label.is_none());
+        assert_eq!(messages[1].line.start_marker, Some(Marker::Arrow));
+    }
+
+    #[test]
+    fn test_line_below_a_box_without_messages() {
+        let art = r#"
+    +-------+
+    | Alice |
+    +-------+
+        |
+        |
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (fragments, unmatched) = endorse_sequences(&span1);
+        assert!(fragments.is_empty());
+        assert_eq!(unmatched.len(), span1.len());
+    }
+
+    #[test]
+    fn test_single_cell_run_beside_a_lifeline() {
+        let art = r#"
+    +-------+
+    | Alice |-
+    +-------+
+        :
+        :
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (fragments, _unmatched) = endorse_sequences(&span1);
+        assert!(fragments.iter().all(|(_, frag_span)| !matches!(
+            frag_span.fragment,
+            Fragment::Message(_)
+        )));
+    }
+}
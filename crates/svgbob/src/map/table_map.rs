@@ -0,0 +1,285 @@
+use crate::{
+    buffer::{CellGrid, FragmentSpan, Span},
+    fragment::Table,
+    Cell, Fragment,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// the character at the intersection of the lines of the table
+const JUNCTION: char = '+';
+/// the character of the lines in between the rows
+const ROW_LINE: char = '-';
+/// the character of the line separating the header rows from the body
+const HEADER_LINE: char = '=';
+/// the character of the lines in between the columns
+const COLUMN_LINE: char = '|';
+
+/// Find the grid tables in this span.
+///
+/// ```ignore
+///   +------+-----+
+///   | name | age |
+///   +======+=====+
+///   | bob  | 4   |
+///   +------+-----+
+/// ```
+/// The grid must be regular, every row line and every column line goes across
+/// the whole table and there must be at least 2 columns, otherwise it is
+/// a rect divided into compartments.
+/// The top line may have a label written in it, which is left as text.
+///
+/// ```ignore
+///   +--[person]--+-----+
+///   | name       | age |
+///   +------------+-----+
+/// ```
+/// The top-left corner must not continue a line to its left or above it,
+/// and the table must not share any of its cells with a table found before it,
+/// otherwise the `+` in the middle of a bigger grid would be the corner of
+/// a smaller table overlapping it.
+///
+/// returns the tables, each one is attached to its top-left corner and localized
+/// to that cell, and the cells that are not part of any table
+pub(crate) fn endorse_tables(span: &Span) -> (Vec<(Cell, FragmentSpan)>, Span) {
+    let cells: BTreeMap<Cell, char> = span.iter().copied().collect();
+    let mut matched: BTreeSet<Cell> = BTreeSet::new();
+    let mut tables = vec![];
+
+    for (cell, ch) in cells.iter() {
+        if *ch != JUNCTION || matched.contains(cell) {
+            continue;
+        }
+        if let Some(frag_span) = match_table(&cells, *cell) {
+            if frag_span.cells().iter().any(|cell| matched.contains(cell)) {
+                continue;
+            }
+            matched.extend(frag_span.cells());
+            tables.push((*cell, frag_span));
+        }
+    }
+    let unmatched = span
+        .iter()
+        .filter(|(cell, _ch)| !matched.contains(cell))
+        .copied()
+        .collect::<Vec<_>>();
+    (tables, Span::from(unmatched))
+}
+
+/// the locations of the junctions along the line starting at `start`,
+/// including `start` itself. The line must end at a junction.
+///
+/// When `allow_label`, a label in between the line characters is skipped over,
+/// and its cells are returned separately. The label may already be taken out
+/// of the cells as a word, leaving a gap in the line.
+fn junctions(
+    cells: &BTreeMap<Cell, char>,
+    start: Cell,
+    line_ch: char,
+    allow_label: bool,
+    next: impl Fn(Cell) -> Cell,
+) -> (Vec<Cell>, Vec<Cell>) {
+    let max_x = cells.keys().map(|cell| cell.x).max().unwrap_or(start.x);
+    let mut junctions = vec![start];
+    let mut label = vec![];
+    let mut previous = start;
+    let mut current = next(start);
+    loop {
+        match cells.get(&current) {
+            Some(&JUNCTION) => junctions.push(current),
+            Some(ch) if *ch == line_ch => (),
+            _ if allow_label && cells.get(&previous) == Some(&line_ch) => {
+                let mut label_end = current;
+                while label_end.x <= max_x
+                    && !matches!(
+                        cells.get(&label_end),
+                        Some(&JUNCTION) | Some(&COLUMN_LINE)
+                    )
+                    && cells.get(&label_end) != Some(&line_ch)
+                {
+                    label_end = next(label_end);
+                }
+                if cells.get(&label_end) != Some(&line_ch) {
+                    break;
+                }
+                while current != label_end {
+                    label.push(current);
+                    current = next(current);
+                }
+            }
+            _ => break,
+        }
+        previous = current;
+        current = next(current);
+    }
+    (junctions, label)
+}
+
+/// match the table with its top-left corner at `top_left`
+fn match_table(
+    cells: &BTreeMap<Cell, char>,
+    top_left: Cell,
+) -> Option<FragmentSpan> {
+    let is_line_outward = matches!(
+        cells.get(&top_left.left()),
+        Some(&ROW_LINE) | Some(&HEADER_LINE)
+    ) || cells.get(&top_left.top()) == Some(&COLUMN_LINE);
+    if is_line_outward {
+        return None;
+    }
+    let (top_junctions, label) =
+        junctions(cells, top_left, ROW_LINE, true, |cell| cell.right());
+    let column_xs: Vec<i32> = top_junctions.iter().map(|cell| cell.x).collect();
+    let row_ys: Vec<i32> =
+        junctions(cells, top_left, COLUMN_LINE, false, |cell| cell.bottom())
+            .0
+            .iter()
+            .map(|cell| cell.y)
+            .collect();
+    // a single column is a rect, which may be divided into compartments
+    if column_xs.len() < 3 || row_ys.len() < 2 {
+        return None;
+    }
+    let (left, right) = (column_xs[0], column_xs[column_xs.len() - 1]);
+    let (top, bottom) = (row_ys[0], row_ys[row_ys.len() - 1]);
+
+    let mut body: Vec<(Cell, char)> = vec![];
+    let mut header = None;
+    for (index, y) in row_ys.iter().enumerate() {
+        let line_ch = cells.get(&Cell::new(left + 1, *y)).copied()?;
+        let is_inner = index > 0 && index < row_ys.len() - 1;
+        if line_ch == HEADER_LINE && is_inner && header.is_none() {
+            header = Some(index - 1);
+        } else if line_ch != ROW_LINE {
+            return None;
+        }
+        for x in left..=right {
+            let cell = Cell::new(x, *y);
+            if label.contains(&cell) {
+                continue;
+            }
+            let expected = if column_xs.contains(&x) {
+                JUNCTION
+            } else {
+                line_ch
+            };
+            if cells.get(&cell) != Some(&expected) {
+                return None;
+            }
+            body.push((cell, expected));
+        }
+    }
+    for x in column_xs.iter() {
+        for y in (top..=bottom).filter(|y| !row_ys.contains(y)) {
+            let cell = Cell::new(*x, y);
+            if cells.get(&cell) != Some(&COLUMN_LINE) {
+                return None;
+            }
+            body.push((cell, COLUMN_LINE));
+        }
+    }
+
+    let local = |x: i32, y: i32| {
+        top_left
+            .localize_cell(Cell::new(x, y))
+            .absolute_position(CellGrid::m())
+    };
+    let table = Table::new(
+        local(left, top),
+        local(right, bottom),
+        column_xs[1..column_xs.len() - 1]
+            .iter()
+            .map(|x| local(*x, top).x)
+            .collect(),
+        row_ys[1..row_ys.len() - 1]
+            .iter()
+            .map(|y| local(left, *y).y)
+            .collect(),
+        header,
+    );
+    Some(FragmentSpan::new(Span::from(body), Fragment::Table(table)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{buffer::CellBuffer, Point};
+
+    #[test]
+    fn test_table_with_header() {
+        let art = r#"
+    +------+-----+
+    | name | age |
+    +======+=====+
+    | bob  | 4   |
+    +------+-----+
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (tables, unmatched) = endorse_tables(&span1);
+        assert_eq!(tables.len(), 1);
+        // only the text inside the cells are left
+        assert_eq!(unmatched.len(), 11);
+        let table = tables[0].1.fragment.as_table().unwrap();
+        assert_eq!(table.start, Point::new(0.5, 1.0));
+        assert_eq!(table.end, Point::new(13.5, 9.0));
+        assert_eq!(table.columns, vec![7.5]);
+        assert_eq!(table.rows, vec![5.0]);
+        assert_eq!(table.header, Some(0));
+    }
+
+    #[test]
+    fn test_single_column_is_not_table() {
+        let art = r#"
+    +------+
+    | name |
+    +------+
+    | age  |
+    +------+
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (tables, _unmatched) = endorse_tables(&span1);
+        assert!(tables.is_empty());
+    }
+
+    #[test]
+    fn test_labelled_table() {
+        let art = r#"
++--[product]-----+--------------+
+|*product_id:int |@name:text    |
++----------------+--------------+
+|1               |Go Pro Hero4  |
++----------------+--------------+
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (tables, unmatched) = endorse_tables(&span1);
+        assert_eq!(tables.len(), 1);
+        let table = tables[0].1.fragment.as_table().unwrap();
+        assert_eq!(table.rows.len(), 1);
+        assert_eq!(table.columns.len(), 1);
+        // the label is left as text
+        assert!(unmatched.iter().any(|(_cell, ch)| *ch == '['));
+    }
+
+    #[test]
+    fn test_inner_junction_is_not_table_corner() {
+        let art = r#"
+    .-----+-----+-----.
+    |     |     |     |
+    +-----+-----+-----+
+    |     |     |     |
+    +-----+-----+-----+
+    |     |     |     |
+    '-----+-----+-----'
+            "#;
+        let cell_buffer = CellBuffer::from(art);
+        let mut spans: Vec<Span> = (&cell_buffer).into();
+        let span1 = spans.remove(0);
+        let (tables, _unmatched) = endorse_tables(&span1);
+        assert!(tables.is_empty());
+    }
+}
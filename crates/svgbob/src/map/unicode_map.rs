@@ -1,12 +1,22 @@
 use crate::{
     buffer::{
-        fragment::PolygonTag::{
-            ArrowBottom, ArrowBottomLeft, ArrowBottomRight, ArrowLeft,
-            ArrowRight, ArrowTop, ArrowTopLeft, ArrowTopRight, DiamondBullet,
+        fragment::{
+            DashStyle::{Dashed, Dotted},
+            Marker,
+            PolygonTag::{
+                ArrowBottom, ArrowBottomLeft, ArrowBottomRight, ArrowLeft,
+                ArrowRight, ArrowTop, ArrowTopLeft, ArrowTopRight,
+                DiamondBullet,
+            },
+            Shade,
         },
         Cell, CellGrid,
     },
-    fragment::{arc, broken_line, circle, line, polygon, rect, Fragment},
+    fragment::{
+        arc, broken_line, circle, dotted_line, heavy_dashed_line, heavy_line,
+        line, marker_line, polygon, rect, shaded_rect, Fragment,
+    },
+    map::braille_map,
     Property,
 };
 use once_cell::sync::Lazy;
@@ -111,14 +121,24 @@ pub static UNICODE_FRAGMENTS: Lazy<BTreeMap<char, Vec<Fragment>>> =
             ('—', vec![line(k, o)]),
             // broken horizontal line
             ('┄', vec![broken_line(k, o)]),
+            ('┅', vec![heavy_dashed_line(k, o, Dashed)]),
+            // double dash horizontal line
+            ('╌', vec![broken_line(k, o)]),
+            ('╍', vec![heavy_dashed_line(k, o, Dashed)]),
+            // dotted horizontal line
+            ('┈', vec![dotted_line(k, o)]),
+            ('┉', vec![heavy_dashed_line(k, o, Dotted)]),
             // vertical line
             ('│', vec![line(c, w)]),
             // broken vertical line
             ('╎', vec![broken_line(c, w)]),
-            // alternate broken vertical line
-            ('┊', vec![broken_line(c, w)]),
+            ('╏', vec![heavy_dashed_line(c, w, Dashed)]),
+            // dotted vertical line
+            ('┊', vec![dotted_line(c, w)]),
+            ('┋', vec![heavy_dashed_line(c, w, Dotted)]),
             // alternate broken vertical line
             ('┆', vec![broken_line(c, w)]),
+            ('┇', vec![heavy_dashed_line(c, w, Dashed)]),
             // slant left
             ('╲', vec![line(a, y)]),
             // slant right
@@ -190,6 +210,63 @@ pub static UNICODE_FRAGMENTS: Lazy<BTreeMap<char, Vec<Fragment>>> =
             //
             ('◀', vec![polygon(vec![j, k, t], true, vec![ArrowLeft])]),
             ('◄', vec![polygon(vec![j, k, t], true, vec![ArrowLeft])]),
+            //
+            // ──→
+            //
+            ('→', vec![marker_line(k, o, false, None, Some(Marker::Arrow))]),
+            ('⟶', vec![marker_line(k, o, false, None, Some(Marker::Arrow))]),
+            //
+            // ←──
+            //
+            ('←', vec![marker_line(o, k, false, None, Some(Marker::Arrow))]),
+            ('⟵', vec![marker_line(o, k, false, None, Some(Marker::Arrow))]),
+            ('↑', vec![marker_line(w, c, false, None, Some(Marker::Arrow))]),
+            ('↓', vec![marker_line(c, w, false, None, Some(Marker::Arrow))]),
+            ('↗', vec![marker_line(u, e, false, None, Some(Marker::Arrow))]),
+            ('↘', vec![marker_line(a, y, false, None, Some(Marker::Arrow))]),
+            ('↙', vec![marker_line(e, u, false, None, Some(Marker::Arrow))]),
+            ('↖', vec![marker_line(y, a, false, None, Some(Marker::Arrow))]),
+            (
+                '↔',
+                vec![marker_line(
+                    k,
+                    o,
+                    false,
+                    Some(Marker::Arrow),
+                    Some(Marker::Arrow),
+                )],
+            ),
+            //
+            // ══⇒
+            //
+            (
+                '⇒',
+                vec![
+                    line(k, m),
+                    line(p, r),
+                    polygon(vec![h, _45, w], true, vec![ArrowRight]),
+                ],
+            ),
+            //
+            // ⇐══
+            //
+            (
+                '⇐',
+                vec![
+                    line(m, o),
+                    line(r, t),
+                    polygon(vec![h, _05, w], true, vec![ArrowLeft]),
+                ],
+            ),
+            (
+                '⇔',
+                vec![
+                    line(l, n),
+                    line(q, s),
+                    polygon(vec![g, _05, v], true, vec![ArrowLeft]),
+                    polygon(vec![i, _45, x], true, vec![ArrowRight]),
+                ],
+            ),
             (
                 '◆',
                 vec![polygon(vec![k, h, o, r, k], true, vec![DiamondBullet])],
@@ -211,6 +288,25 @@ pub static UNICODE_FRAGMENTS: Lazy<BTreeMap<char, Vec<Fragment>>> =
             ('▇', vec![rect(_01, y, true, false)]),
             // 8/8
             ('█', vec![rect(a, y, true, false)]),
+            // shades
+            ('░', vec![shaded_rect(a, y, Shade::Light)]),
+            ('▒', vec![shaded_rect(a, y, Shade::Medium)]),
+            ('▓', vec![shaded_rect(a, y, Shade::Dark)]),
+            // halves
+            ('▌', vec![rect(a, w, true, false)]),
+            ('▐', vec![rect(c, y, true, false)]),
+            ('▀', vec![rect(a, o, true, false)]),
+            // quadrants
+            ('▖', vec![rect(k, w, true, false)]),
+            ('▗', vec![rect(m, y, true, false)]),
+            ('▘', vec![rect(a, m, true, false)]),
+            ('▝', vec![rect(c, o, true, false)]),
+            ('▙', vec![rect(a, w, true, false), rect(m, y, true, false)]),
+            ('▛', vec![rect(a, o, true, false), rect(k, w, true, false)]),
+            ('▜', vec![rect(a, o, true, false), rect(m, y, true, false)]),
+            ('▟', vec![rect(c, o, true, false), rect(k, y, true, false)]),
+            ('▚', vec![rect(a, m, true, false), rect(m, y, true, false)]),
+            ('▞', vec![rect(c, o, true, false), rect(k, w, true, false)]),
             // L shape bottom-left box
             ('⌊', vec![line(a, u), line(u, w)]),
             // not equal sign
@@ -233,6 +329,39 @@ pub static UNICODE_FRAGMENTS: Lazy<BTreeMap<char, Vec<Fragment>>> =
             ('┤', vec![line(c, w), line(k, m)]),
             ('┬', vec![line(k, o), line(m, w)]),
             ('┴', vec![line(k, o), line(c, m)]),
+            // heavy horizontal line
+            ('━', vec![heavy_line(k, o)]),
+            // heavy vertical line
+            ('┃', vec![heavy_line(c, w)]),
+            ('┏', vec![heavy_line(m, o), heavy_line(m, w)]),
+            ('┓', vec![heavy_line(m, k), heavy_line(m, w)]),
+            ('┗', vec![heavy_line(c, m), heavy_line(m, o)]),
+            ('┛', vec![heavy_line(c, m), heavy_line(k, m)]),
+            ('┣', vec![heavy_line(c, w), heavy_line(m, o)]),
+            ('┫', vec![heavy_line(c, w), heavy_line(k, m)]),
+            ('┳', vec![heavy_line(k, o), heavy_line(m, w)]),
+            ('┻', vec![heavy_line(k, o), heavy_line(c, m)]),
+            ('╋', vec![heavy_line(c, w), heavy_line(k, o)]),
+            // mixed light and heavy corners
+            ('┍', vec![line(m, w), heavy_line(m, o)]),
+            ('┎', vec![heavy_line(m, w), line(m, o)]),
+            ('┑', vec![line(m, w), heavy_line(k, m)]),
+            ('┒', vec![heavy_line(m, w), line(k, m)]),
+            ('┕', vec![line(c, m), heavy_line(m, o)]),
+            ('┖', vec![heavy_line(c, m), line(m, o)]),
+            ('┙', vec![line(c, m), heavy_line(k, m)]),
+            ('┚', vec![heavy_line(c, m), line(k, m)]),
+            // mixed light and heavy junctions
+            ('┝', vec![line(c, w), heavy_line(m, o)]),
+            ('┠', vec![heavy_line(c, w), line(m, o)]),
+            ('┥', vec![line(c, w), heavy_line(k, m)]),
+            ('┨', vec![heavy_line(c, w), line(k, m)]),
+            ('┯', vec![heavy_line(k, o), line(m, w)]),
+            ('┰', vec![line(k, o), heavy_line(m, w)]),
+            ('┷', vec![heavy_line(k, o), line(c, m)]),
+            ('┸', vec![line(k, o), heavy_line(c, m)]),
+            ('┿', vec![line(c, w), heavy_line(k, o)]),
+            ('╂', vec![heavy_line(c, w), line(k, o)]),
             /// rounded top left
             ('╭', vec![arc(o, r, unit2), line(r, w)]),
             /// rounded top right
@@ -356,7 +485,9 @@ pub static UNICODE_FRAGMENTS: Lazy<BTreeMap<char, Vec<Fragment>>> =
         ];
         // sort the fragments first before putting into the btreemap
         let mut btree = BTreeMap::new();
-        for (ch, mut fragments) in map.into_iter() {
+        for (ch, mut fragments) in
+            map.into_iter().chain(braille_map::braille_fragments())
+        {
             fragments.sort();
             btree.insert(ch, fragments);
         }
@@ -1,3 +1,58 @@
+use crate::{CharacterMap, StencilRegistry};
+use std::{error, fmt, str::FromStr};
+
+/// the scale at the default settings, where a cell is 8 units wide
+pub(crate) const BASE_SCALE: f32 = 8.0;
+
+/// the stroke width at the default settings, the markers and the arrows
+/// in the ascii art are sized for this width
+pub(crate) const BASE_STROKE_WIDTH: f32 = 2.0;
+
+/// The shape of the arrow heads, used in both the arrow markers
+/// and the arrows drawn with `>` `<` `^` `v`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum ArrowHead {
+    /// a filled triangle
+    #[default]
+    Triangle,
+    /// an open V
+    Chevron,
+    /// a filled triangle with its back notched in
+    Barbed,
+    /// the outline of a triangle, also accepted as `line` when parsed
+    Hollow,
+}
+
+/// Error when parsing the name of an arrow head
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownArrowHead(pub String);
+
+impl fmt::Display for UnknownArrowHead {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown arrow head `{}`, expecting one of: triangle, chevron, barbed, hollow, line",
+            self.0
+        )
+    }
+}
+
+impl error::Error for UnknownArrowHead {}
+
+impl FromStr for ArrowHead {
+    type Err = UnknownArrowHead;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "triangle" => Ok(ArrowHead::Triangle),
+            "chevron" => Ok(ArrowHead::Chevron),
+            "barbed" => Ok(ArrowHead::Barbed),
+            "hollow" | "line" => Ok(ArrowHead::Hollow),
+            _ => Err(UnknownArrowHead(input.to_string())),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Settings {
     /// font-size of the text
@@ -12,8 +67,18 @@ pub struct Settings {
     pub stroke_color: String,
     /// the width of the stroke
     pub stroke_width: f32,
+    /// the width of the stroke of heavy lines and shapes
+    pub heavy_stroke_width: f32,
     /// the scale multiplier
     pub scale: f32,
+    /// the radius of the corners of rounded rects drawn with `.` and `'`,
+    /// in the unit of a cell width.
+    /// When not set, the radius follows the ascii art
+    pub rounded_corner_radius: Option<f32>,
+    /// the radius of the `+` corners of rects and connected lines,
+    /// in the unit of a cell width.
+    /// When not set, the `+` corners are sharp
+    pub plus_corner_radius: Option<f32>,
     /// flag whether to include the big rectangle as backdrop
     /// for all of the svg shapes
     pub include_backdrop: bool,
@@ -21,6 +86,30 @@ pub struct Settings {
     pub include_styles: bool,
     /// flag whether to include the def of markers, etc in the svg
     pub include_defs: bool,
+    /// the multiplier to the size of the markers and the arrow heads
+    pub marker_scale: f32,
+    /// flag whether the markers and the arrow heads grow with the stroke width,
+    /// otherwise the markers are sized relative to the cell
+    pub scale_markers_with_stroke: bool,
+    /// the shape of the arrow heads
+    pub arrow_head: ArrowHead,
+    /// flag whether to smooth the staircases of lines, such as `_.-'`
+    /// into curves
+    pub smooth_staircases: bool,
+    /// flag whether to accept the rects and circles which are drawn with small
+    /// gaps or misalignments, such as a missing corner, as the intended shape
+    /// and suggest the repairs to their ascii art
+    pub tolerant_shapes: bool,
+    /// the share of letters and digits a run of characters must be above
+    /// to be taken as a word and rendered as text, such as `I/O` and `x86_64`.
+    /// A value of 1.0 turns off the word detection
+    pub text_strictness: f32,
+    /// user defined stencils which replaces the matching ascii art
+    pub stencils: StencilRegistry,
+    /// the characters and their corresponding properties and fragments.
+    /// These are not used by the recognizers of the tables, the sequence diagrams,
+    /// the gates, the circuit symbols and the line endings
+    pub character_map: CharacterMap,
 }
 
 impl Default for Settings {
@@ -31,11 +120,22 @@ impl Default for Settings {
             fill_color: "black".into(),
             background: "white".into(),
             stroke_color: "black".into(),
-            stroke_width: 2.0,
-            scale: 8.0,
+            stroke_width: BASE_STROKE_WIDTH,
+            heavy_stroke_width: 4.0,
+            scale: BASE_SCALE,
+            rounded_corner_radius: None,
+            plus_corner_radius: None,
             include_backdrop: true,
             include_styles: true,
             include_defs: true,
+            marker_scale: 1.0,
+            scale_markers_with_stroke: true,
+            arrow_head: ArrowHead::default(),
+            smooth_staircases: false,
+            tolerant_shapes: false,
+            text_strictness: 0.5,
+            stencils: StencilRegistry::default(),
+            character_map: CharacterMap::default(),
         }
     }
 }
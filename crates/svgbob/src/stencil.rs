@@ -0,0 +1,408 @@
+//! User defined stencils.
+//!
+//! A stencil is an ascii art template which is replaced by a user supplied
+//! svg snippet or a list of fragments whenever it is found in the diagram.
+//!
+//! ```ignore
+//!     .-----.
+//!     |?????|
+//!     |-----|
+//!     '-----'
+//! ```
+//! Whitespace in the template must also be empty in the diagram, while the
+//! wildcard `?` cells can contain anything. The wildcard cells are not consumed
+//! by the stencil, so labels placed there are rendered as usual.
+use crate::{
+    buffer::{FragmentSpan, Span, StringBuffer},
+    fragment::Snippet,
+    Cell, Fragment,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error, fmt,
+    str::FromStr,
+};
+
+/// cells with this char in the template can contain any char or be empty
+pub const WILDCARD: char = '?';
+
+/// What a matching stencil is replaced with
+#[derive(Debug, Clone)]
+pub enum StencilShape {
+    /// raw svg elements, drawn relative to the top-left of the template
+    /// at the default scale, where each cell is 8 units wide and 16 units tall
+    Svg(String),
+    /// fragments, positioned relative to the top-left of the template
+    Fragments(Vec<Fragment>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Stencil {
+    pub name: String,
+    /// the non-whitespace, non-wildcard cells of the template
+    cells: BTreeMap<Cell, char>,
+    /// the cells that are matched with anything
+    wildcards: BTreeSet<Cell>,
+    /// the bottom-right most cell of the template
+    bottom_right: Cell,
+    shape: StencilShape,
+}
+
+impl Stencil {
+    /// create a stencil which is replaced with the svg snippet
+    pub fn with_svg(name: &str, template: &str, svg: &str) -> Self {
+        Self::new(name, template, StencilShape::Svg(svg.to_string()))
+    }
+
+    /// create a stencil which is replaced with the fragments
+    pub fn with_fragments(
+        name: &str,
+        template: &str,
+        fragments: Vec<Fragment>,
+    ) -> Self {
+        Self::new(name, template, StencilShape::Fragments(fragments))
+    }
+
+    /// create a stencil, the template is localized to the top-left most
+    /// non-whitespace char
+    pub fn new(name: &str, template: &str, shape: StencilShape) -> Self {
+        let mut template_cells = vec![];
+        for (y, line) in StringBuffer::from(template).iter().enumerate() {
+            for (x, ch) in line.iter().enumerate() {
+                if *ch != '\0' && !ch.is_whitespace() {
+                    template_cells.push((Cell::new(x as i32, y as i32), *ch));
+                }
+            }
+        }
+        let min_x = template_cells.iter().map(|(cell, _)| cell.x).min();
+        let min_y = template_cells.iter().map(|(cell, _)| cell.y).min();
+        let top_left =
+            Cell::new(min_x.unwrap_or_default(), min_y.unwrap_or_default());
+
+        let mut cells = BTreeMap::new();
+        let mut wildcards = BTreeSet::new();
+        let mut bottom_right = Cell::new(0, 0);
+        for (cell, ch) in template_cells {
+            let cell = top_left.localize_cell(cell);
+            bottom_right.x = bottom_right.x.max(cell.x);
+            bottom_right.y = bottom_right.y.max(cell.y);
+            if ch == WILDCARD {
+                wildcards.insert(cell);
+            } else {
+                cells.insert(cell, ch);
+            }
+        }
+        Stencil {
+            name: name.to_string(),
+            cells,
+            wildcards,
+            bottom_right,
+            shape,
+        }
+    }
+
+    /// check if the template matches the cells with the top-left
+    /// of the template placed at `loc`
+    fn is_match(&self, cells: &BTreeMap<Cell, char>, loc: Cell) -> bool {
+        (0..=self.bottom_right.y).all(|y| {
+            (0..=self.bottom_right.x).all(|x| {
+                let local = Cell::new(x, y);
+                let cell = Cell::new(loc.x + x, loc.y + y);
+                self.wildcards.contains(&local)
+                    || cells.get(&cell) == self.cells.get(&local)
+            })
+        })
+    }
+
+    /// the fragments this stencil is replaced with at `loc`
+    fn fragments(&self, loc: Cell) -> Vec<Fragment> {
+        match &self.shape {
+            StencilShape::Svg(svg) => {
+                let snippet = Snippet::new(
+                    Cell::new(0, 0).a(),
+                    self.bottom_right.y(),
+                    svg.clone(),
+                );
+                vec![snippet.absolute_position(loc).into()]
+            }
+            StencilShape::Fragments(fragments) => fragments
+                .iter()
+                .map(|frag| frag.absolute_position(loc))
+                .collect(),
+        }
+    }
+
+    /// replace all the occurence of this stencil in the cells.
+    /// The matched cells are removed from `cells`
+    fn endorse(&self, cells: &mut BTreeMap<Cell, char>) -> Vec<FragmentSpan> {
+        let (anchor, anchor_ch) = match self.cells.iter().next() {
+            Some((anchor, ch)) => (*anchor, *ch),
+            None => return vec![],
+        };
+        let candidates: Vec<Cell> = cells
+            .iter()
+            .filter(|(_cell, ch)| **ch == anchor_ch)
+            .map(|(cell, _ch)| Cell::new(cell.x - anchor.x, cell.y - anchor.y))
+            .collect();
+
+        let mut accepted = vec![];
+        for loc in candidates {
+            if self.is_match(cells, loc) {
+                let matched: Vec<(Cell, char)> = self
+                    .cells
+                    .keys()
+                    .map(|local| Cell::new(loc.x + local.x, loc.y + local.y))
+                    .filter_map(|cell| cells.remove(&cell).map(|ch| (cell, ch)))
+                    .collect();
+                let span = Span::from(matched);
+                accepted.extend(
+                    self.fragments(loc)
+                        .into_iter()
+                        .map(|frag| FragmentSpan::new(span.clone(), frag)),
+                );
+            }
+        }
+        accepted
+    }
+}
+
+/// A collection of stencils, tried in the order they are registered
+#[derive(Debug, Clone, Default)]
+pub struct StencilRegistry {
+    stencils: Vec<Stencil>,
+}
+
+impl StencilRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, stencil: Stencil) {
+        self.stencils.push(stencil);
+    }
+
+    pub fn extend(&mut self, other: StencilRegistry) {
+        self.stencils.extend(other.stencils);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Stencil> {
+        self.stencils.iter().find(|stencil| stencil.name == name)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stencils.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.stencils.len()
+    }
+
+    /// replace the cells which match any of the stencils,
+    /// the matched cells are removed from `cells`
+    pub(crate) fn endorse(
+        &self,
+        cells: &mut BTreeMap<Cell, char>,
+    ) -> Vec<FragmentSpan> {
+        self.stencils
+            .iter()
+            .flat_map(|stencil| stencil.endorse(cells))
+            .collect()
+    }
+}
+
+/// Error when parsing a stencil file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StencilError {
+    /// a line that is not part of any stencil
+    UnexpectedLine(usize),
+    /// the stencil has no `--` separator followed by the svg
+    MissingSvg(String),
+    /// the stencil has no ascii template
+    EmptyTemplate(String),
+}
+
+impl fmt::Display for StencilError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StencilError::UnexpectedLine(line) => write!(
+                f,
+                "line {} is not part of a stencil, stencils starts with `== name`",
+                line
+            ),
+            StencilError::MissingSvg(name) => {
+                write!(f, "stencil `{}` has no svg after `--`", name)
+            }
+            StencilError::EmptyTemplate(name) => {
+                write!(f, "stencil `{}` has an empty template", name)
+            }
+        }
+    }
+}
+
+impl error::Error for StencilError {}
+
+/// (name, template lines, svg lines if the `--` separator is reached)
+type ParsedStencil<'a> = (String, Vec<&'a str>, Option<Vec<&'a str>>);
+
+fn register_parsed(
+    registry: &mut StencilRegistry,
+    parsed: Option<ParsedStencil>,
+) -> Result<(), StencilError> {
+    if let Some((name, template, svg)) = parsed {
+        let svg = svg.ok_or_else(|| StencilError::MissingSvg(name.clone()))?;
+        let template = template.join("\n");
+        if template.trim().is_empty() {
+            return Err(StencilError::EmptyTemplate(name));
+        }
+        let svg = svg.join("\n");
+        if svg.trim().is_empty() {
+            return Err(StencilError::MissingSvg(name));
+        }
+        registry.register(Stencil::with_svg(&name, &template, svg.trim()));
+    }
+    Ok(())
+}
+
+/// Parse stencils from a file with the format:
+///
+/// ```ignore
+/// # comments are allowed before the first stencil
+/// == server
+/// .-----.
+/// |?????|
+/// '-----'
+/// --
+/// <rect x="4" y="8" width="48" height="32" rx="4" class="solid nofill"/>
+/// ```
+/// The lines after `== name` up to `--` is the ascii template,
+/// the lines after it up to the next stencil is the svg snippet.
+impl FromStr for StencilRegistry {
+    type Err = StencilError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut registry = StencilRegistry::new();
+        let mut current: Option<ParsedStencil> = None;
+
+        for (i, line) in input.lines().enumerate() {
+            if let Some(name) = line.strip_prefix("==") {
+                register_parsed(&mut registry, current.take())?;
+                current = Some((name.trim().to_string(), vec![], None));
+            } else if let Some((_name, template, svg)) = &mut current {
+                match svg {
+                    Some(svg) => svg.push(line),
+                    None if line.trim_end() == "--" => *svg = Some(vec![]),
+                    None => template.push(line),
+                }
+            } else if !line.trim().is_empty() && !line.starts_with('#') {
+                return Err(StencilError::UnexpectedLine(i + 1));
+            }
+        }
+        register_parsed(&mut registry, current.take())?;
+        Ok(registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{buffer::CellBuffer, fragment, Point};
+
+    #[test]
+    fn test_stencil_template() {
+        let stencil = Stencil::with_svg(
+            "server",
+            r#"
+            .---.
+            |???|
+            '---'
+            "#,
+            "<rect/>",
+        );
+        assert_eq!(stencil.bottom_right, Cell::new(4, 2));
+        assert_eq!(stencil.cells.len(), 12);
+        assert_eq!(stencil.wildcards.len(), 3);
+    }
+
+    #[test]
+    fn test_stencil_match_with_label() {
+        let stencil = Stencil::with_fragments(
+            "box",
+            r#"
+            +--+
+            |??|
+            +--+
+            "#,
+            vec![fragment::line(Point::new(0.0, 0.0), Point::new(4.0, 6.0))],
+        );
+        let art = r#"
+        +--+
+        |db|----
+        +--+
+        "#;
+        let mut cells: BTreeMap<Cell, char> = CellBuffer::from(art)
+            .iter()
+            .map(|(cell, ch)| (*cell, *ch))
+            .collect();
+        let accepted = stencil.endorse(&mut cells);
+        assert_eq!(accepted.len(), 1);
+        assert_eq!(
+            accepted[0].fragment,
+            fragment::line(Point::new(8.0, 2.0), Point::new(12.0, 8.0))
+        );
+        // the label and the line are left untouched
+        assert_eq!(cells.len(), 6);
+    }
+
+    #[test]
+    fn test_stencil_whitespace_must_be_empty() {
+        let stencil = Stencil::with_svg("box", "+--+\n|  |\n+--+", "<rect/>");
+        let mut cells: BTreeMap<Cell, char> =
+            CellBuffer::from("+--+\n|db|\n+--+")
+                .iter()
+                .map(|(cell, ch)| (*cell, *ch))
+                .collect();
+        assert!(stencil.endorse(&mut cells).is_empty());
+        assert_eq!(cells.len(), 12);
+    }
+
+    #[test]
+    fn test_parse_stencils() {
+        let input = r#"
+# shared icons
+== server
+.---.
+|???|
+'---'
+--
+<rect x="4" y="8" width="32" height="32"/>
+
+== cloud
+ .-.
+( ? )
+ '-'
+--
+<ellipse cx="20" cy="24" rx="20" ry="16"/>
+"#;
+        let registry: StencilRegistry = input.parse().expect("must parse");
+        assert_eq!(registry.len(), 2);
+        let cloud = registry.get("cloud").expect("must have cloud");
+        assert_eq!(cloud.bottom_right, Cell::new(4, 2));
+        assert_eq!(cloud.wildcards.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_stencils_errors() {
+        assert_eq!(
+            "server".parse::<StencilRegistry>().err(),
+            Some(StencilError::UnexpectedLine(1))
+        );
+        assert_eq!(
+            "== server\n.---.".parse::<StencilRegistry>().err(),
+            Some(StencilError::MissingSvg("server".to_string()))
+        );
+        assert_eq!(
+            "== server\n--\n<rect/>".parse::<StencilRegistry>().err(),
+            Some(StencilError::EmptyTemplate("server".to_string()))
+        );
+    }
+}
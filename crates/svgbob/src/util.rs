@@ -90,6 +90,73 @@ pub fn pad(v: f32) -> f32 {
     }
 }
 
+/// fit a cubic bezier curve through `points`, starting at the first point
+/// going along `start_tangent` and ending at the last point coming from
+/// `end_tangent`, which points back into the curve.
+///
+/// The distance of the control points along the tangents is solved by least
+/// squares with the points parameterized by their distance along the path.
+///
+/// returns the 2 control points of the curve
+pub fn fit_cubic_bezier(
+    points: &[Point],
+    start_tangent: Point,
+    end_tangent: Point,
+) -> (Point, Point) {
+    let first = points[0];
+    let last = points[points.len() - 1];
+    let dot = |a: Point, b: Point| a.x * b.x + a.y * b.y;
+
+    let lengths: Vec<f32> = points
+        .windows(2)
+        .scan(0.0, |total, pair| {
+            *total += pair[0].distance(&pair[1]);
+            Some(*total)
+        })
+        .collect();
+    let path_length = lengths[lengths.len() - 1];
+
+    let mut c = [[0.0; 2]; 2];
+    let mut x = [0.0; 2];
+    for (point, length) in points.iter().skip(1).zip(lengths.iter()) {
+        let u = length / path_length;
+        let b0 = (1.0 - u).powi(3);
+        let b1 = 3.0 * u * (1.0 - u).powi(2);
+        let b2 = 3.0 * u.powi(2) * (1.0 - u);
+        let b3 = u.powi(3);
+        let a1 = start_tangent.scale(b1);
+        let a2 = end_tangent.scale(b2);
+        c[0][0] += dot(a1, a1);
+        c[0][1] += dot(a1, a2);
+        c[1][1] += dot(a2, a2);
+        let rest = *point - (first.scale(b0 + b1) + last.scale(b2 + b3));
+        x[0] += dot(rest, a1);
+        x[1] += dot(rest, a2);
+    }
+    c[1][0] = c[0][1];
+
+    let det = c[0][0] * c[1][1] - c[0][1] * c[1][0];
+    let fallback = first.distance(&last) / 3.0;
+    let (alpha1, alpha2) = if det.abs() > f32::EPSILON {
+        (
+            (x[0] * c[1][1] - x[1] * c[0][1]) / det,
+            (c[0][0] * x[1] - c[1][0] * x[0]) / det,
+        )
+    } else {
+        (fallback, fallback)
+    };
+    // the control points must not fold back behind the ends of the curve
+    let (alpha1, alpha2) = if alpha1 <= 0.0 || alpha2 <= 0.0 {
+        (fallback, fallback)
+    } else {
+        (alpha1, alpha2)
+    };
+    (
+        first + start_tangent.scale(alpha1),
+        last + end_tangent.scale(alpha2),
+    )
+}
+
 /// this is parser module which provides parsing for identifier for
 /// extracting the css tag of inside of a shape fragment
 pub mod parser {
@@ -465,6 +532,24 @@ mod tests {
         assert!(!is_collinear(&f, &a, &b));
     }
 
+    #[test]
+    fn test_fit_cubic_bezier_on_a_straight_line() {
+        let points = [
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 2.0),
+            Point::new(3.0, 3.0),
+        ];
+        let tangent = Point::new(1.0, 1.0).normalize();
+        let (control1, control2) =
+            fit_cubic_bezier(&points, tangent, tangent.scale(-1.0));
+        // the control points lie on the line, in between the ends
+        assert!(is_collinear(&points[0], &control1, &points[3]));
+        assert!(is_collinear(&points[0], &control2, &points[3]));
+        assert!(control1.x > 0.0 && control1.x < control2.x);
+        assert!(control2.x < 3.0);
+    }
+
     #[test]
     fn test_padding() {
         assert_eq!(10.0, pad(9.1));
@@ -1,4 +1,4 @@
-use svgbob::Settings;
+use svgbob::{ArrowHead, Settings};
 
 #[test]
 fn rect1() {
@@ -84,3 +84,850 @@ fn arrow_down() {
     println!("{}", svg);
     assert_eq!(expected, svg);
 }
+
+#[test]
+fn l_shaped_polygon() {
+    let bob = r#"
+    +------+
+    | {a}  |
+    |      +-----+
+    |            |
+    +------------+
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="152" height="112" class="svgbob">
+  <polygon points="36,24 92,24 92,56 140,56 140,88 36,88" class="nofill a"></polygon>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn parallelogram() {
+    let bob = r#"
+      ________
+     /       /
+    /_______/
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="120" height="80" class="svgbob">
+  <polygon points="48,32 112,32 96,64 32,64" class="nofill"></polygon>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn cylinder() {
+    let bob = r#"
+    .------.
+    '------'
+    |      |
+    | {db} |
+    '------'
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="104" height="112" class="svgbob">
+  <path d="M 36,32 L 36,80 A 28,8 0,0,0 92,80 L 92,32 A 28,8 0,0,0 36,32 Z M 36,32 A 28,8 0,0,0 92,32" class="solid nofill db"></path>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn custom_character() {
+    use svgbob::{buffer::CellGrid, fragment::line, CharacterMap};
+
+    let bob = r#"
+    ≈≈≈≈≈≈
+    "#;
+
+    let mut character_map = CharacterMap::new();
+    character_map
+        .register_fragments('≈', vec![line(CellGrid::k(), CellGrid::o())]);
+    let settings = Settings {
+        character_map,
+        ..Settings::for_debug()
+    };
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="88" height="48" class="svgbob">
+  <line x1="32" y1="24" x2="80" y2="24" class="solid"></line>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &settings);
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn heavy_rect() {
+    let bob = r#"
+    ┏━━━━━━┓
+    ┃      ┃
+    ┗━━━━━━┛
+     ━━┿━━
+       │
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="104" height="112" class="svgbob">
+  <rect x="36" y="24" width="56" height="32" class="solid nofill heavy" rx="0"></rect>
+  <line x1="40" y1="72" x2="80" y2="72" class="solid heavy"></line>
+  <line x1="60" y1="64" x2="60" y2="96" class="solid"></line>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn braille_dots() {
+    let bob = "⠀⢀⡠\n⠊⠀⠀";
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="32" height="48" class="svgbob">
+  <circle cx="14" cy="14" r="1" class="filled"></circle>
+  <circle cx="22" cy="10" r="1" class="filled"></circle>
+  <circle cx="18" cy="14" r="1" class="filled"></circle>
+  <circle cx="6" cy="18" r="1" class="filled"></circle>
+  <circle cx="2" cy="22" r="1" class="filled"></circle>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn block_bar_chart() {
+    let bob = r#"
+    ██
+    ██ ▄▄
+    ██ ██ ░░
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="104" height="80" class="svgbob">
+  <rect x="32" y="16" width="16" height="48" class="solid filled" rx="0"></rect>
+  <rect x="56" y="40" width="16" height="24" class="solid filled" rx="0"></rect>
+  <rect x="80" y="48" width="16" height="16" class="solid filled light_shade" rx="0"></rect>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn unicode_arrows() {
+    let bob = r#"
+    ←──→  ══⇒  │
+               ↓
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="136" height="64" class="svgbob">
+  <line x1="32" y1="24" x2="64" y2="24" class="solid start_marked_arrow end_marked_arrow"></line>
+  <line x1="80" y1="24" x2="100" y2="24" class="solid"></line>
+  <line x1="80" y1="28" x2="100" y2="28" class="solid"></line>
+  <polygon points="100,20 104,26 100,32" class="filled"></polygon>
+  <line x1="124" y1="16" x2="124" y2="48" class="solid end_marked_arrow"></line>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn dash_styles() {
+    let bob = r#"
+    ┄┄┄┄  ┈┈┈┈  ~~~~  ......
+    ╏           +----+
+    ╏           :    :
+    ╏           +----+
+
+    - - - -  -.-.-.-
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="232" height="128" class="svgbob">
+  <line x1="32" y1="104" x2="88" y2="104" class="broken"></line>
+  <line x1="104" y1="104" x2="160" y2="104" class="dash_dot"></line>
+  <line x1="180" y1="24" x2="220" y2="24" class="dotted"></line>
+  <rect x="132" y="40" width="40" height="32" class="dotted nofill" rx="0"></rect>
+  <line x1="32" y1="24" x2="64" y2="24" class="broken"></line>
+  <line x1="36" y1="32" x2="36" y2="80" class="broken heavy"></line>
+  <line x1="80" y1="24" x2="112" y2="24" class="dotted"></line>
+  <line x1="128" y1="24" x2="160" y2="24" class="broken"></line>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn dots_in_prose() {
+    let bob = r#"
+    ok...   wait ... then   etc..   Hmm..
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="336" height="48" class="svgbob">
+  <text x="34" y="28" >ok...</text>
+  <text x="98" y="28" >wait</text>
+  <text x="138" y="28" >...</text>
+  <text x="170" y="28" >then</text>
+  <text x="226" y="28" >etc..</text>
+  <text x="290" y="28" >Hmm..</text>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn crows_foot_markers() {
+    let bob = r#"
+    ||-----o|
+
+    >|-----o<
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="112" height="80" class="svgbob">
+  <line x1="32" y1="24" x2="104" y2="24" class="solid start_marked_one end_marked_zero_or_one"></line>
+  <line x1="32" y1="56" x2="104" y2="56" class="solid start_marked_one_or_many end_marked_zero_or_many"></line>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn arrows_are_not_crows_feet() {
+    let bob = r#"
+    ->-----
+
+    |->--+
+
+    ------<'     `>----
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="192" height="112" class="svgbob">
+  <line x1="32" y1="24" x2="40" y2="24" class="solid"></line>
+  <polygon points="40,20 48,24 40,28" class="filled"></polygon>
+  <line x1="48" y1="24" x2="88" y2="24" class="solid"></line>
+  <polygon points="48,52 56,56 48,60" class="filled"></polygon>
+  <line x1="56" y1="56" x2="76" y2="56" class="solid"></line>
+  <polygon points="88,84 80,88 88,92" class="filled"></polygon>
+  <text x="90" y="92" >&#39;</text>
+  <text x="138" y="92" >`</text>
+  <polygon points="144,84 152,88 144,92" class="filled"></polygon>
+  <g>
+    <line x1="36" y1="48" x2="36" y2="64" class="solid"></line>
+    <line x1="36" y1="56" x2="48" y2="56" class="solid"></line>
+  </g>
+  <g>
+    <line x1="32" y1="88" x2="80" y2="88" class="solid"></line>
+    <line x1="88" y1="84" x2="80" y2="88" class="solid"></line>
+    <line x1="80" y1="88" x2="88" y2="92" class="solid"></line>
+  </g>
+  <g>
+    <line x1="144" y1="84" x2="152" y2="88" class="solid"></line>
+    <line x1="152" y1="88" x2="144" y2="92" class="solid"></line>
+    <line x1="152" y1="88" x2="184" y2="88" class="solid"></line>
+  </g>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn uml_markers() {
+    let bob = r#"
+    <|-----|>
+
+    <>-----<*>
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="120" height="80" class="svgbob">
+  <line x1="32" y1="24" x2="104" y2="24" class="solid start_marked_clear_arrow end_marked_clear_arrow"></line>
+  <line x1="32" y1="56" x2="112" y2="56" class="solid start_marked_open_diamond end_marked_filled_diamond"></line>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn uml_attached_markers() {
+    let bob = r#"
+    +---+         +---+
+    | A |o--------| B |
+    |   |*--------|   |
+    +---+         +---+
+
+    o--------o
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="192" height="128" class="svgbob">
+  <line x1="80" y1="40" x2="72" y2="40" class="solid end_marked_open_diamond"></line>
+  <line x1="80" y1="56" x2="72" y2="56" class="solid end_marked_filled_diamond"></line>
+  <rect x="36" y="24" width="32" height="48" class="solid nofill" rx="0"></rect>
+  <text x="50" y="44" >A</text>
+  <text x="162" y="44" >B</text>
+  <line x1="36" y1="104" x2="104" y2="104" class="solid start_marked_open_circle"></line>
+  <circle cx="108" cy="104" r="3" class="nofill"></circle>
+  <g>
+    <line x1="80" y1="40" x2="148" y2="40" class="solid"></line>
+    <line x1="80" y1="56" x2="148" y2="56" class="solid"></line>
+    <path d="M 148,24 L 180,24 L 180,72 L 148,72 L 148,24 Z" class="solid nofill"></path>
+  </g>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn mid_line_arrows() {
+    let bob = r#"
+    ---->>-------
+
+    ----<<----
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="144" height="80" class="svgbob">
+  <g class="mid_marked">
+    <line x1="32" y1="24" x2="136" y2="24" class="solid"></line>
+    <polygon points="72,24 64,28 64,20" class="filled"></polygon>
+    <polygon points="80,24 72,28 72,20" class="filled"></polygon>
+  </g>
+  <g class="mid_marked">
+    <line x1="32" y1="56" x2="112" y2="56" class="solid"></line>
+    <polygon points="64,56 72,52 72,60" class="filled"></polygon>
+    <polygon points="72,56 80,52 80,60" class="filled"></polygon>
+  </g>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn circuit_symbols() {
+    let bob = r#"
+    o--/\/\/--||--mmm-->|--o
+                           |
+                         -----
+                          ---
+                           -
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="248" height="112" class="svgbob">
+  <line x1="36" y1="24" x2="56" y2="24" class="solid start_marked_open_circle"></line>
+  <polygon points="184,16 196,24 184,32" class="filled"></polygon>
+  <line x1="196" y1="16" x2="196" y2="32" class="solid"></line>
+  <line x1="196" y1="24" x2="220" y2="24" class="solid end_marked_open_circle"></line>
+  <line x1="208" y1="72" x2="232" y2="72" class="solid"></line>
+  <line x1="216" y1="88" x2="224" y2="88" class="solid"></line>
+  <g>
+    <line x1="116" y1="16" x2="116" y2="32" class="solid"></line>
+    <path d="M 56,24 L 60,20 L 68,28 L 76,20 L 84,28 L 92,20 L 96,24 L 116,24" class="solid nofill"></path>
+  </g>
+  <g>
+    <line x1="124" y1="16" x2="124" y2="32" class="solid"></line>
+    <path d="M 124,24 L 144,24 A 4,4 0,0,1 152,24 A 4,4 0,0,1 160,24 A 4,4 0,0,1 168,24 L 184,24" class="solid nofill"></path>
+  </g>
+  <g>
+    <line x1="220" y1="28" x2="220" y2="56" class="solid"></line>
+    <line x1="200" y1="56" x2="240" y2="56" class="solid"></line>
+  </g>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn logic_gates() {
+    let bob = r#"
+       .--.
+    ---|   \         |\
+       |    )--------| >o---
+    ---|   /         |/
+       '--'
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="232" height="112" class="svgbob">
+  <path d="M 60,24 L 72,24 A 32,32 0,0,1 72,88 L 60,88 Z M 56,40 L 60,40 M 56,72 L 60,72" class="solid nofill and_gate"></path>
+  <line x1="32" y1="40" x2="56" y2="40" class="solid"></line>
+  <path d="M 172,40 L 192,56 L 172,72 Z M 192,56 A 4,4 0,1,0 200,56 A 4,4 0,1,0 192,56 M 168,56 L 172,56" class="solid nofill not_gate"></path>
+  <line x1="104" y1="56" x2="168" y2="56" class="solid"></line>
+  <line x1="200" y1="56" x2="224" y2="56" class="solid"></line>
+  <line x1="32" y1="72" x2="56" y2="72" class="solid"></line>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn uml_class_compartments() {
+    let bob = r#"
++-----------------+
+| Person          |
++-----------------+
+| name: String    |
+| age: int        |
++-----------------+
+| greet           |
++-----------------+
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="160" height="160" class="svgbob">
+  <rect x="4" y="24" width="144" height="112" class="solid nofill" rx="0"></rect>
+  <line x1="4" y1="56" x2="148" y2="56" class="solid"></line>
+  <line x1="4" y1="104" x2="148" y2="104" class="solid"></line>
+  <text x="18" y="44" class="compartment_0">Person</text>
+  <text x="66" y="76" class="compartment_1">String</text>
+  <text x="58" y="92" class="compartment_1">int</text>
+  <text x="18" y="124" class="compartment_2">greet</text>
+  <text x="18" y="76" class="compartment_1">name:</text>
+  <text x="18" y="92" class="compartment_1">age:</text>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn grid_table() {
+    let bob = r#"
++------+-------+
+| name | age   |
++======+=======+
+| alice| 30    |
++------+-------+
+| bob  | 4     |
++------+-------+
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="136" height="144" class="svgbob">
+  <g class="table">
+    <rect x="4" y="24" width="120" height="96" class="solid nofill table_border"></rect>
+    <line x1="4" y1="56" x2="124" y2="56" class="solid table_header"></line>
+    <line x1="4" y1="88" x2="124" y2="88" class="solid table_row"></line>
+    <line x1="60" y1="24" x2="60" y2="120" class="solid table_column"></line>
+  </g>
+  <text x="18" y="44" class="header row_0 column_0">name</text>
+  <text x="74" y="44" class="header row_0 column_1">age</text>
+  <text x="18" y="76" class="row_1 column_0">alice</text>
+  <text x="74" y="76" class="row_1 column_1">30</text>
+  <text x="18" y="108" class="row_2 column_0">bob</text>
+  <text x="74" y="108" class="row_2 column_1">4</text>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn sequence_diagram() {
+    let bob = r#"
+ +-------+          +--------+
+ | Alice |          |  Bob   |
+ +-------+          +--------+
+     :                   :
+     :      hello        :
+     +------------------>:
+     :                   :
+     :      hi back      :
+     :<------------------+
+     :                   :
+     :   get_user()      :
+     +------------------>:
+     :                  +-+
+     :    call          | |
+     +----------------->| |
+     :                  +-+
+     :                   :
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="248" height="304" class="svgbob">
+  <rect x="12" y="24" width="64" height="32" class="solid nofill" rx="0"></rect>
+  <text x="26" y="44" >Alice</text>
+  <rect x="164" y="24" width="72" height="32" class="solid nofill" rx="0"></rect>
+  <text x="186" y="44" >Bob</text>
+  <line x1="44" y1="56" x2="44" y2="288" class="dotted lifeline"></line>
+  <g class="lifeline_group">
+    <line x1="204" y1="56" x2="204" y2="288" class="dotted lifeline"></line>
+    <rect x="196" y="216" width="16" height="48" class="solid nofill activation"></rect>
+  </g>
+  <g class="message">
+    <line x1="44" y1="104" x2="204" y2="104" class="solid end_marked_arrow"></line>
+    <text x="98" y="92">hello</text>
+  </g>
+  <g class="message">
+    <line x1="44" y1="152" x2="204" y2="152" class="solid start_marked_arrow"></line>
+    <text x="98" y="140">hi back</text>
+  </g>
+  <g class="message">
+    <line x1="44" y1="200" x2="204" y2="200" class="solid end_marked_arrow"></line>
+    <text x="74" y="188">get_user()</text>
+  </g>
+  <g class="message">
+    <line x1="44" y1="248" x2="196" y2="248" class="solid end_marked_arrow"></line>
+    <text x="82" y="236">call</text>
+  </g>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn arrows_next_to_labels() {
+    let bob = r#"
+    client---->server
+    Alice<----Bob
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="176" height="64" class="svgbob">
+  <text x="34" y="28" >client</text>
+  <line x1="80" y1="24" x2="112" y2="24" class="solid"></line>
+  <polygon points="112,20 120,24 112,28" class="filled"></polygon>
+  <text x="122" y="28" >server</text>
+  <text x="34" y="44" >Alice</text>
+  <polygon points="80,36 72,40 80,44" class="filled"></polygon>
+  <line x1="80" y1="40" x2="112" y2="40" class="solid"></line>
+  <text x="114" y="44" >Bob</text>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn configurable_corner_radius() {
+    let bob = r#"
+    +------+   .------.
+    | box  |   | soft |
+    +------+   '------'
+
+      +--------+
+      |        |
+      +-----+  +---->
+    "#;
+
+    let settings = Settings {
+        rounded_corner_radius: Some(0.25),
+        plus_corner_radius: Some(0.5),
+        ..Settings::for_debug()
+    };
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="192" height="144" class="svgbob">
+  <rect x="36" y="24" width="56" height="32" class="solid nofill" rx="4"></rect>
+  <text x="50" y="44" >box</text>
+  <rect x="124" y="24" width="56" height="32" class="solid nofill" rx="2"></rect>
+  <text x="138" y="44" >soft</text>
+  <polygon points="160,116 168,120 160,124" class="filled"></polygon>
+  <g>
+    <path d="M 100,120 L 56,120 A 4,4 0,0,1 52,116 L 52,92 A 4,4 0,0,1 56,88 L 120,88 A 4,4 0,0,1 124,92 L 124,116 A 4,4 0,0,0 128,120 L 160,120" class="solid nofill"></path>
+  </g>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &settings);
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn chevron_arrow_heads() {
+    let bob = r#"
+    ----->
+      |
+      v
+    "#;
+
+    let settings = Settings {
+        arrow_head: ArrowHead::Chevron,
+        marker_scale: 1.5,
+        ..Settings::for_debug()
+    };
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="88" height="80" class="svgbob">
+  <line x1="32" y1="24" x2="72" y2="24" class="solid"></line>
+  <line x1="72" y1="24" x2="80" y2="24" class="solid"></line>
+  <line x1="68" y1="18" x2="80" y2="24" class="solid"></line>
+  <line x1="80" y1="24" x2="68" y2="30" class="solid"></line>
+  <line x1="52" y1="32" x2="52" y2="52" class="solid"></line>
+  <line x1="52" y1="52" x2="52" y2="64" class="solid"></line>
+  <line x1="46" y1="46" x2="52" y2="64" class="solid"></line>
+  <line x1="58" y1="46" x2="52" y2="64" class="solid"></line>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &settings);
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn chevron_mid_line_arrows() {
+    let bob = r#"
+    ---->>---->
+    "#;
+
+    let settings = Settings {
+        arrow_head: ArrowHead::Chevron,
+        marker_scale: 2.0,
+        ..Settings::for_debug()
+    };
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="128" height="48" class="svgbob">
+  <g class="mid_marked">
+    <line x1="32" y1="24" x2="112" y2="24" class="solid"></line>
+    <line x1="72" y1="24" x2="56" y2="32" class="solid"></line>
+    <line x1="56" y1="16" x2="72" y2="24" class="solid"></line>
+    <line x1="80" y1="24" x2="64" y2="32" class="solid"></line>
+    <line x1="64" y1="16" x2="80" y2="24" class="solid"></line>
+  </g>
+  <line x1="112" y1="24" x2="120" y2="24" class="solid"></line>
+  <line x1="104" y1="16" x2="120" y2="24" class="solid"></line>
+  <line x1="120" y1="24" x2="104" y2="32" class="solid"></line>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &settings);
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn hollow_arrow_heads() {
+    let bob = r#"
+    ----->
+    "#;
+
+    let settings = Settings {
+        arrow_head: ArrowHead::Hollow,
+        marker_scale: 1.5,
+        ..Settings::for_debug()
+    };
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="88" height="48" class="svgbob">
+  <line x1="32" y1="24" x2="72" y2="24" class="solid"></line>
+  <polygon points="68,18 80,24 68,30" class="nofill"></polygon>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &settings);
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+
+    // the same arrow head is filled with the default triangle
+    let filled = Settings {
+        arrow_head: ArrowHead::Triangle,
+        ..settings
+    };
+    let filled_svg = svgbob::to_svg_with_settings(bob, &filled);
+    assert!(filled_svg.contains(r#"class="filled""#));
+    assert!(!svg.contains(r#"class="filled""#));
+}
+
+#[test]
+fn smooth_staircase() {
+    let bob = r#"
+                 _.-'
+             _.-'
+     +--+_.-'
+     |  |
+     +--+
+    "#;
+
+    let settings = Settings {
+        smooth_staircases: true,
+        ..Settings::for_debug()
+    };
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="176" height="112" class="svgbob">
+  <text x="162" y="28" >&#39;</text>
+  <g>
+    <path d="M 160,24 C 130.21565,38.89217 98.912445,51.63502 68,64" class="solid nofill"></path>
+    <path d="M 44,56 L 68,56 L 68,88 L 44,88 L 44,56 Z" class="solid nofill"></path>
+  </g>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &settings);
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn smooth_staircase_in_chain() {
+    let bob = r#"
+                  .------
+              _.-'
+          _.-'
+    -----'
+    "#;
+
+    let settings = Settings {
+        smooth_staircases: true,
+        ..Settings::for_debug()
+    };
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="208" height="96" class="svgbob">
+  <g>
+    <path d="M 140,32 A 8,8 0,0,1 148,24 L 200,24" class="solid nofill"></path>
+  </g>
+  <g>
+    <path d="M 148,32 A 8,8 0,0,1 140,40 C 115.92415,49.63034 95.40216,60.29892 72,72 L 32,72" class="solid nofill"></path>
+  </g>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &settings);
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn words_with_punctuations() {
+    let bob = r#"
+I/O x86_64 a_b it's foo_bar_baz wait... greet()
+
++----+
+|name|
++----+
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="384" height="112" class="svgbob">
+  <rect x="4" y="56" width="40" height="32" class="solid nofill" rx="0"></rect>
+  <text x="10" y="76" >name</text>
+  <text x="2" y="28" >I/O</text>
+  <text x="34" y="28" >x86_64</text>
+  <text x="90" y="28" >a_b</text>
+  <text x="122" y="28" >it&#39;s</text>
+  <text x="162" y="28" >foo_bar_baz</text>
+  <text x="258" y="28" >wait...</text>
+  <text x="322" y="28" >greet()</text>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn tolerant_shapes() {
+    let bob = r#"
+ -----+    +-----+     .-----     .--.
+|     |    |      |    |     |   (    )
++-----+    +-----+     '-----'    `--
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="320" height="80" class="svgbob">
+  <rect x="4" y="24" width="48" height="32" class="solid nofill" rx="0"></rect>
+  <rect x="92" y="24" width="48" height="32" class="solid nofill" rx="0"></rect>
+  <rect x="188" y="24" width="48" height="32" class="solid nofill" rx="4"></rect>
+  <circle cx="288" cy="40" r="20" class="nofill"></circle>
+</svg>"#;
+
+    let settings = Settings {
+        tolerant_shapes: true,
+        ..Settings::for_debug()
+    };
+    let svg = svgbob::to_svg_with_settings(bob, &settings);
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+
+    let repairs: Vec<String> = svgbob::suggest_repairs(bob, &settings)
+        .iter()
+        .map(|repair| repair.to_string())
+        .collect();
+    assert_eq!(
+        repairs,
+        vec![
+            "(0,1) '+'",
+            "(17,2) '|'",
+            "(18,2) ' '",
+            "(29,1) '.'",
+            "(37,3) '\\''"
+        ]
+    );
+
+    // the shapes are left as lines and arcs by default
+    let repairs = svgbob::suggest_repairs(bob, &Settings::for_debug());
+    assert!(repairs.is_empty());
+}
+
+#[test]
+fn isometric_box() {
+    let bob = r#"
+    +----------+
+   /          /|
+  /  disk    / |
+ +----------+  |
+ |          |  +
+ |  server  | /
+ |          |/
+ +----------+
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="136" height="160" class="svgbob">
+  <g class="isometric_box">
+    <polygon points="12,72 36,24 124,24 100,72" class="nofill top"></polygon>
+    <polygon points="100,72 124,24 124,88 100,136" class="nofill side"></polygon>
+    <polygon points="12,72 100,72 100,136 12,136" class="nofill front"></polygon>
+  </g>
+  <text x="42" y="60" class="top_face">disk</text>
+  <text x="34" y="108" class="front_face">server</text>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
+
+#[test]
+fn isometric_box_with_connector() {
+    let bob = r#"
+    +----------+
+   /          /|
+  /  disk    / +---->
+ +----------+  |
+ |          |  +
+ |  server  | /
+ |          |/
+ +----------+
+    "#;
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="176" height="160" class="svgbob">
+  <g class="isometric_box">
+    <polygon points="12,72 36,24 124,24 100,72" class="nofill top"></polygon>
+    <polygon points="100,72 124,24 124,88 100,136" class="nofill side"></polygon>
+    <polygon points="12,72 100,72 100,136 12,136" class="nofill front"></polygon>
+  </g>
+  <text x="42" y="60" class="top_face">disk</text>
+  <text x="34" y="108" class="front_face">server</text>
+  <line x1="124" y1="56" x2="160" y2="56" class="solid"></line>
+  <polygon points="160,52 168,56 160,60" class="filled"></polygon>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &Settings::for_debug());
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
@@ -0,0 +1,37 @@
+use svgbob::{Settings, Stencil, StencilRegistry};
+
+#[test]
+fn stencil_with_label() {
+    let bob = r#"
+    .---.
+    |web|---
+    '---'
+    "#;
+
+    let mut stencils = StencilRegistry::new();
+    stencils.register(Stencil::with_svg(
+        "server",
+        r#"
+        .---.
+        |???|
+        '---'
+        "#,
+        r#"<rect x="4" y="8" width="32" height="32"/>"#,
+    ));
+    let settings = Settings {
+        stencils,
+        ..Settings::for_debug()
+    };
+
+    let expected = r#"<svg xmlns="http://www.w3.org/2000/svg" width="104" height="80" class="svgbob">
+  <g transform="translate(32,16) scale(1)" class="stencil">
+    <rect x="4" y="8" width="32" height="32"/>
+  </g>
+  <text x="42" y="44" >web</text>
+  <line x1="72" y1="40" x2="96" y2="40" class="solid"></line>
+</svg>"#;
+
+    let svg = svgbob::to_svg_with_settings(bob, &settings);
+    println!("{}", svg);
+    assert_eq!(expected, svg);
+}
@@ -0,0 +1,37 @@
+/// render every diagram in the test_data directory,
+/// to catch the panics anywhere in the pipeline
+#[test]
+fn test_render_all_test_data() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/test_data");
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .expect("should read the test_data directory")
+        .map(|entry| entry.expect("should be a directory entry").path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "bob"))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+
+    for path in paths {
+        println!("rendering {}", path.display());
+        let bob = std::fs::read_to_string(&path).expect("should read");
+        let svg = svgbob::to_svg(&bob);
+        assert!(svg.starts_with("<svg"), "{}", path.display());
+    }
+}
+
+/// the product table in demo.bob, with a label in its top line,
+/// is drawn as a single table and not as tables overlapping each other
+#[test]
+fn test_demo_product_table() {
+    let demo = include_str!("../test_data/demo.bob");
+    let product: String = demo
+        .lines()
+        .skip_while(|line| !line.starts_with("+--[product]"))
+        .take_while(|line| !line.trim().is_empty())
+        .map(|line| format!("{line}\n"))
+        .collect();
+    assert_eq!(product.lines().count(), 8);
+
+    let svg = svgbob::to_svg(&product);
+    assert_eq!(svg.matches(r#"<g class="table">"#).count(), 1);
+}
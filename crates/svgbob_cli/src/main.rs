@@ -4,7 +4,7 @@ extern crate clap;
 
 extern crate svgbob;
 
-use svgbob::Settings;
+use svgbob::{Settings, StencilRegistry};
 
 use clap::ArgMatches;
 use std::error::Error;
@@ -47,6 +47,10 @@ fn main() {
              .long("stroke-width")
              .takes_value(true)
              .help("stroke width for all lines (default: 2)"))
+        .arg(Arg::with_name("heavy-stroke-width")
+             .long("heavy-stroke-width")
+             .takes_value(true)
+             .help("stroke width for heavy lines (default: 4)"))
         .arg(Arg::with_name("stroke-color")
              .long("stroke-color")
              .takes_value(true)
@@ -55,6 +59,39 @@ fn main() {
              .long("scale")
              .takes_value(true)
              .help("scale the entire svg (dimensions, font size, stroke width) by this factor (default: 1)"))
+        .arg(Arg::with_name("rounded-corner-radius")
+             .long("rounded-corner-radius")
+             .takes_value(true)
+             .help("radius of the corners of rounded rects, in cell widths (default: as drawn)"))
+        .arg(Arg::with_name("plus-corner-radius")
+             .long("plus-corner-radius")
+             .takes_value(true)
+             .help("round the '+' corners of rects and lines with this radius, in cell widths (default: sharp)"))
+        .arg(Arg::with_name("marker-scale")
+             .long("marker-scale")
+             .takes_value(true)
+             .help("scale the markers and arrow heads by this factor (default: 1)"))
+        .arg(Arg::with_name("markers-follow-scale")
+             .long("markers-follow-scale")
+             .help("size the markers relative to the cell instead of the stroke width"))
+        .arg(Arg::with_name("arrow-head")
+             .long("arrow-head")
+             .takes_value(true)
+             .help("shape of the arrow heads: triangle, chevron, barbed or hollow, line is the same as hollow (default: triangle)"))
+        .arg(Arg::with_name("smooth")
+             .long("smooth")
+             .help("smooth the staircases of lines such as _.-' into curves"))
+        .arg(Arg::with_name("tolerant")
+             .long("tolerant")
+             .help("accept rects and circles with small gaps or misalignments, and print the repairs to their ascii art to stderr"))
+        .arg(Arg::with_name("text-strictness")
+             .long("text-strictness")
+             .takes_value(true)
+             .help("share of letters and digits a word such as I/O must be above to be rendered as text, 1.0 turns it off (default: 0.5)"))
+        .arg(Arg::with_name("stencils")
+             .long("stencils")
+             .takes_value(true)
+             .help("file of user defined stencils, which replace the matching ascii art with svg"))
         .subcommand(SubCommand::with_name("build")
             .about("Batch convert files to svg.")
             .version("0.0.1")
@@ -127,6 +164,10 @@ fn main() {
         settings.stroke_width = stroke_width;
     }
 
+    if let Some(heavy_stroke_width) = parse_value_of(&args, "heavy-stroke-width") {
+        settings.heavy_stroke_width = heavy_stroke_width;
+    }
+
     if let Some(stroke_color) = parse_value_of(&args, "stroke-color") {
         settings.stroke_color = stroke_color;
     }
@@ -136,6 +177,39 @@ fn main() {
         settings.scale *= s;
     }
 
+    settings.rounded_corner_radius = parse_value_of(&args, "rounded-corner-radius");
+
+    settings.plus_corner_radius = parse_value_of(&args, "plus-corner-radius");
+
+    if let Some(marker_scale) = parse_value_of(&args, "marker-scale") {
+        settings.marker_scale = marker_scale;
+    }
+
+    settings.scale_markers_with_stroke = !args.is_present("markers-follow-scale");
+
+    if let Some(arrow_head) = parse_value_of(&args, "arrow-head") {
+        settings.arrow_head = arrow_head;
+    }
+
+    settings.smooth_staircases = args.is_present("smooth");
+
+    if let Some(text_strictness) = parse_value_of(&args, "text-strictness") {
+        settings.text_strictness = text_strictness;
+    }
+
+    if let Some(file) = args.value_of("stencils") {
+        settings.stencils = read_stencils(file);
+    }
+
+    // the repairs are suggested with the same settings the svg is drawn with,
+    // so the stencils are not mistaken for broken shapes
+    settings.tolerant_shapes = args.is_present("tolerant");
+    if settings.tolerant_shapes {
+        for repair in svgbob::suggest_repairs(&bob, &settings) {
+            eprintln!("repair {}", repair);
+        }
+    }
+
     let svg = svgbob::to_svg_with_settings(&*bob, &settings);
 
     if let Some(file) = args.value_of("output") {
@@ -177,6 +251,37 @@ where
     });
 }
 
+fn read_stencils(file: &str) -> StencilRegistry {
+    use std::io::Write;
+
+    let content = match fs::read_to_string(file) {
+        Ok(content) => content,
+        Err(e) => {
+            writeln!(
+                &mut std::io::stderr(),
+                "Failed to open stencils file {}: {}",
+                file,
+                e
+            )
+            .unwrap();
+            exit(1);
+        }
+    };
+    match content.parse::<StencilRegistry>() {
+        Ok(stencils) => stencils,
+        Err(e) => {
+            writeln!(
+                &mut std::io::stderr(),
+                "Invalid stencils file {}: {}",
+                file,
+                e
+            )
+            .unwrap();
+            exit(1);
+        }
+    }
+}
+
 // Batch convert files to svg
 // use svgbob build -i inputdir/*.bob -o outdir/
 fn build(args: &ArgMatches) -> Result<(), Box<dyn Error>> {